@@ -4,6 +4,8 @@ use serde_json::Value;
 
 const DEFAULT_RPC_URL: &str = "http://localhost:8080";
 const CHALLENGE_ID: &str = "bounty-challenge";
+/// Scores and weights are reported as fixed-point parts per billion.
+const PPB_SCALE: f64 = 1_000_000_000.0;
 
 #[derive(Parser)]
 #[command(name = "bounty-cli")]
@@ -160,7 +162,8 @@ async fn cmd_leaderboard(rpc_url: &str, limit: usize) -> Result<()> {
         let net_points = entry
             .get("net_points")
             .and_then(|v| v.as_f64())
-            .unwrap_or(0.0);
+            .unwrap_or(0.0)
+            / PPB_SCALE;
         let valid = entry
             .get("valid_issues")
             .and_then(|v| v.as_u64())
@@ -173,7 +176,7 @@ async fn cmd_leaderboard(rpc_url: &str, limit: usize) -> Result<()> {
             .get("star_count")
             .and_then(|v| v.as_u64())
             .unwrap_or(0);
-        let weight = entry.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0);
+        let weight = entry.get("score").and_then(|v| v.as_f64()).unwrap_or(0.0) / PPB_SCALE;
 
         println!(
             "{:<6} {:<15} {:<20} {:<12.2} {:<8} {:<8} {:<10} {:<10.4}",
@@ -260,7 +263,7 @@ async fn cmd_status(rpc_url: &str, hotkey: &str) -> Result<()> {
         .get("invalid_issues_count")
         .and_then(|v| v.as_u64())
         .unwrap_or(0);
-    let weight = body.get("weight").and_then(|v| v.as_f64()).unwrap_or(0.0);
+    let weight = body.get("weight").and_then(|v| v.as_f64()).unwrap_or(0.0) / PPB_SCALE;
 
     let balance = body.get("balance");
     let duplicates = balance
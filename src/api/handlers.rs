@@ -1,3 +1,4 @@
+use alloc::collections::BTreeSet;
 use alloc::string::String;
 use alloc::vec::Vec;
 use bincode::Options;
@@ -5,12 +6,20 @@ use platform_challenge_sdk_wasm::host_functions::host_consensus_get_submission_c
 use platform_challenge_sdk_wasm::{WasmRouteRequest, WasmRouteResponse};
 
 use crate::types::{
-    BountySubmission, IssueRecord, RegisterRequest, StatsResponse, StatusResponse, TimeoutConfig,
-    UserBalance,
+    AuthConfig, ClaimLimits, DecayConfig, DuplicateConfig, IssueContent, IssueRecord, LabelTiers,
+    LeaderboardSnapshot, PointMultipliers, QualityConfig, RankHistoryEntry, RegisterRequest,
+    RepositoryRegistry, ScoringConfig, SimulationRequest, StarTargets, StatsResponse,
+    StatusResponse, TimeoutConfig, UidMapping, UserBalance, UserStars, ValidatorSet,
+    WeightsResponse,
 };
-use crate::{consensus, scoring, storage, validation};
+use crate::validation::ClaimPayload;
+use crate::{consensus, quality, scoring, signature, storage, validation};
 
 const MAX_ROUTE_BODY_SIZE: usize = 1_048_576;
+/// Hotkey allowed to change challenge settings, fixed at build time. Without
+/// it, settings cannot be changed through routes.
+const OWNER_HOTKEY: Option<&str> = option_env!("BOUNTY_OWNER_HOTKEY");
+const MAX_SIMULATED_ISSUES: u32 = 10_000;
 
 fn bincode_options_route_body() -> impl Options {
     bincode::DefaultOptions::new()
@@ -37,6 +46,13 @@ fn bad_request_response() -> WasmRouteResponse {
     }
 }
 
+fn forbidden_response() -> WasmRouteResponse {
+    WasmRouteResponse {
+        status: 403,
+        body: bincode::serialize(&false).unwrap_or_default(),
+    }
+}
+
 fn not_found_response() -> WasmRouteResponse {
     WasmRouteResponse {
         status: 404,
@@ -52,6 +68,23 @@ fn is_authenticated(request: &WasmRouteRequest) -> bool {
         .unwrap_or(false)
 }
 
+fn is_owner(request: &WasmRouteRequest) -> bool {
+    match (OWNER_HOTKEY, request.auth_hotkey.as_deref()) {
+        (Some(owner), Some(caller)) => !owner.is_empty() && caller == owner,
+        _ => false,
+    }
+}
+
+/// The authenticated hotkey, if it is in the validator set.
+fn validator_hotkey(request: &WasmRouteRequest) -> Option<&str> {
+    let hotkey = request.auth_hotkey.as_deref()?;
+    storage::get_validator_set()
+        .hotkeys
+        .iter()
+        .any(|v| v == hotkey)
+        .then_some(hotkey)
+}
+
 fn get_param<'a>(request: &'a WasmRouteRequest, name: &str) -> Option<&'a str> {
     request
         .params
@@ -60,11 +93,89 @@ fn get_param<'a>(request: &'a WasmRouteRequest, name: &str) -> Option<&'a str> {
         .map(|(_, v)| v.as_str())
 }
 
+fn get_query_param<'a>(request: &'a WasmRouteRequest, name: &str) -> Option<&'a str> {
+    get_param(request, name).or_else(|| {
+        let (_, query) = request.path.split_once('?')?;
+        query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .find(|(k, _)| *k == name)
+            .map(|(_, v)| v)
+    })
+}
+
 pub fn handle_leaderboard(_request: &WasmRouteRequest) -> WasmRouteResponse {
     let entries = storage::get_leaderboard();
     ok_response(bincode::serialize(&entries).unwrap_or_default())
 }
 
+pub fn handle_leaderboard_history(request: &WasmRouteRequest) -> WasmRouteResponse {
+    let epoch = match get_query_param(request, "epoch") {
+        Some(e) => match e.parse::<u64>() {
+            Ok(e) => e,
+            Err(_) => return bad_request_response(),
+        },
+        None => {
+            let epochs = storage::get_snapshot_epochs();
+            return ok_response(bincode::serialize(&epochs).unwrap_or_default());
+        }
+    };
+
+    let live = storage::get_leaderboard();
+    if let Some(live_epoch) = live.first().map(|e| e.last_epoch) {
+        if epoch >= live_epoch {
+            let snapshot = LeaderboardSnapshot {
+                epoch: live_epoch,
+                entries: live,
+            };
+            return ok_response(bincode::serialize(&snapshot).unwrap_or_default());
+        }
+    }
+
+    let snapshot_epoch = storage::get_snapshot_epochs()
+        .into_iter()
+        .filter(|e| *e <= epoch)
+        .max();
+    let snapshot = snapshot_epoch.and_then(|e| {
+        storage::get_leaderboard_snapshot(e)
+            .map(|entries| LeaderboardSnapshot { epoch: e, entries })
+    });
+    match snapshot {
+        Some(s) => ok_response(bincode::serialize(&s).unwrap_or_default()),
+        None => not_found_response(),
+    }
+}
+
+pub fn handle_hotkey_rank_history(request: &WasmRouteRequest) -> WasmRouteResponse {
+    let hotkey = match get_param(request, "hotkey") {
+        Some(h) => h,
+        None => return bad_request_response(),
+    };
+
+    let mut history = storage::get_rank_history(hotkey);
+    if let Some(entry) = storage::get_leaderboard()
+        .iter()
+        .find(|e| e.hotkey == hotkey)
+    {
+        history.push(RankHistoryEntry {
+            epoch: entry.last_epoch,
+            rank: entry.rank,
+            score: entry.score,
+            net_points: entry.net_points,
+        });
+    }
+
+    ok_response(bincode::serialize(&history).unwrap_or_default())
+}
+
+pub fn handle_rebuild_leaderboard(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    scoring::rebuild_leaderboard();
+    ok_response(bincode::serialize(&true).unwrap_or_default())
+}
+
 pub fn handle_stats(_request: &WasmRouteRequest) -> WasmRouteResponse {
     let total_submissions = host_consensus_get_submission_count() as u64;
     let active_miners = storage::get_active_miner_count();
@@ -94,8 +205,9 @@ pub fn handle_status(request: &WasmRouteRequest) -> WasmRouteResponse {
                 github_username: None,
                 valid_issues_count: 0,
                 invalid_issues_count: 0,
+                pending_issues_count: 0,
                 balance: UserBalance::default(),
-                weight: 0.0,
+                weight: 0,
             };
             return ok_response(bincode::serialize(&status).unwrap_or_default());
         }
@@ -103,16 +215,25 @@ pub fn handle_status(request: &WasmRouteRequest) -> WasmRouteResponse {
 
     let balance = storage::get_user_balance(hotkey);
     let weight = if balance.is_penalized {
-        0.0
+        0
     } else {
-        scoring::calculate_weight_from_points(balance.valid_count, balance.star_count)
+        let (_, decayed_points) = scoring::hotkey_issue_points(hotkey, &balance);
+        scoring::calculate_weight_from_points(
+            decayed_points,
+            balance.star_count,
+            &storage::get_scoring_config(),
+        )
     };
 
+    let pending_issues_count =
+        scoring::count_pending_issues(&storage::get_synced_issues(), &reg.github_username);
+
     let status = StatusResponse {
         registered: true,
         github_username: Some(reg.github_username),
         valid_issues_count: balance.valid_count,
         invalid_issues_count: balance.invalid_count,
+        pending_issues_count,
         balance,
         weight,
     };
@@ -133,10 +254,22 @@ pub fn handle_register(request: &WasmRouteRequest) -> WasmRouteResponse {
     };
 
     let hotkey = request.auth_hotkey.as_deref().unwrap_or(&reg.hotkey);
+    if !signature::verify_registration(&reg, hotkey) {
+        return unauthorized_response();
+    }
+    let auth_config = storage::get_auth_config();
+    if !signature::is_timestamp_fresh(reg.timestamp, &auth_config)
+        || !signature::consume_signature(hotkey, reg.timestamp, &reg.signature, &auth_config)
+    {
+        return unauthorized_response();
+    }
 
     let result = storage::register_user(&reg.github_username, hotkey);
     if result {
         storage::ensure_hotkey_tracked(hotkey);
+        let now = signature::host_now_secs();
+        storage::observe_epoch(scoring::current_epoch(), now);
+        storage::record_registered_at(hotkey, now);
     }
     ok_response(bincode::serialize(&result).unwrap_or_default())
 }
@@ -149,26 +282,82 @@ pub fn handle_claim(request: &WasmRouteRequest) -> WasmRouteResponse {
         return bad_request_response();
     }
 
-    let submission: BountySubmission = match bincode_options_route_body().deserialize(&request.body)
-    {
-        Ok(s) => s,
-        Err(_) => return bad_request_response(),
+    let payload = match ClaimPayload::decode(&request.body, MAX_ROUTE_BODY_SIZE as u64) {
+        Some(p) => p,
+        None => return bad_request_response(),
     };
+    if !payload.verify_signature() {
+        return bad_request_response();
+    }
 
+    let submission = payload.into_submission();
     if !validation::validate_submission(&submission) {
         return bad_request_response();
     }
+    if !validation::matches_registration(&submission) {
+        return forbidden_response();
+    }
+    if !signature::consume_signature(
+        &submission.hotkey,
+        submission.timestamp,
+        &submission.signature,
+        &storage::get_auth_config(),
+    ) {
+        return bad_request_response();
+    }
+
+    let limits = storage::get_claim_limits();
+    let epoch = scoring::current_epoch();
+    if let Err(code) = validation::check_claim_limits(&submission, &limits, epoch) {
+        let result = validation::limited_claim_result(&submission, code, &limits, epoch);
+        return WasmRouteResponse {
+            status: 429,
+            body: bincode::serialize(&result).unwrap_or_default(),
+        };
+    }
 
     let synced_issues = storage::get_synced_issues();
     let result = validation::process_claims(&submission, &synced_issues);
 
     if !result.claimed.is_empty() {
-        scoring::rebuild_leaderboard();
+        scoring::update_leaderboard_entry(&submission.hotkey);
     }
 
     ok_response(bincode::serialize(&result).unwrap_or_default())
 }
 
+/// Checks a signed claim exactly as `/claim` would without recording it.
+/// The signature is verified but not consumed, so the same payload can be
+/// submitted to `/claim` afterwards.
+pub fn handle_claim_preview(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+
+    let payload = match ClaimPayload::decode(&request.body, MAX_ROUTE_BODY_SIZE as u64) {
+        Some(p) => p,
+        None => return bad_request_response(),
+    };
+    if !payload.verify_signature() {
+        return bad_request_response();
+    }
+
+    let submission = payload.into_submission();
+    if !validation::validate_submission(&submission) {
+        return bad_request_response();
+    }
+    if !validation::matches_registration(&submission) {
+        return forbidden_response();
+    }
+
+    let synced_issues = storage::get_synced_issues();
+    let preview = validation::preview_claims(&submission, &synced_issues);
+    ok_response(bincode::serialize(&preview).unwrap_or_default())
+}
+
 pub fn handle_issues(_request: &WasmRouteRequest) -> WasmRouteResponse {
     let issues = storage::get_synced_issues();
     ok_response(bincode::serialize(&issues).unwrap_or_default())
@@ -179,6 +368,16 @@ pub fn handle_issues_pending(_request: &WasmRouteRequest) -> WasmRouteResponse {
     ok_response(bincode::serialize(&issues).unwrap_or_default())
 }
 
+pub fn handle_issues_duplicates(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let links = storage::get_duplicate_links();
+    ok_response(bincode::serialize(&links).unwrap_or_default())
+}
+
+pub fn handle_issues_reviews(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let reviews = storage::get_quality_reviews();
+    ok_response(bincode::serialize(&reviews).unwrap_or_default())
+}
+
 pub fn handle_hotkey_details(request: &WasmRouteRequest) -> WasmRouteResponse {
     let hotkey = match get_param(request, "hotkey") {
         Some(h) => h,
@@ -192,16 +391,25 @@ pub fn handle_hotkey_details(request: &WasmRouteRequest) -> WasmRouteResponse {
 
     let balance = storage::get_user_balance(hotkey);
     let weight = if balance.is_penalized {
-        0.0
+        0
     } else {
-        scoring::calculate_weight_from_points(balance.valid_count, balance.star_count)
+        let (_, decayed_points) = scoring::hotkey_issue_points(hotkey, &balance);
+        scoring::calculate_weight_from_points(
+            decayed_points,
+            balance.star_count,
+            &storage::get_scoring_config(),
+        )
     };
 
+    let pending_issues_count =
+        scoring::count_pending_issues(&storage::get_synced_issues(), &reg.github_username);
+
     let status = StatusResponse {
         registered: true,
         github_username: Some(reg.github_username),
         valid_issues_count: balance.valid_count,
         invalid_issues_count: balance.invalid_count,
+        pending_issues_count,
         balance,
         weight,
     };
@@ -228,7 +436,9 @@ pub fn handle_invalid(request: &WasmRouteRequest) -> WasmRouteResponse {
             reason.as_deref(),
         );
         if result {
-            scoring::rebuild_leaderboard();
+            if let Some(hotkey) = storage::get_hotkey_by_github(&github_username) {
+                scoring::update_leaderboard_entry(&hotkey);
+            }
         }
         ok_response(bincode::serialize(&result).unwrap_or_default())
     } else {
@@ -240,6 +450,10 @@ pub fn handle_sync_propose(request: &WasmRouteRequest) -> WasmRouteResponse {
     if !is_authenticated(request) {
         return unauthorized_response();
     }
+    let validator = match validator_hotkey(request) {
+        Some(v) => v,
+        None => return forbidden_response(),
+    };
     if request.body.len() > MAX_ROUTE_BODY_SIZE {
         return bad_request_response();
     }
@@ -247,10 +461,23 @@ pub fn handle_sync_propose(request: &WasmRouteRequest) -> WasmRouteResponse {
     if let Ok((validator_id, issues)) =
         bincode_options_route_body().deserialize::<(String, Vec<IssueRecord>)>(&request.body)
     {
-        let result = consensus::propose_sync_data(&validator_id, &issues);
+        if validator_id != validator {
+            return forbidden_response();
+        }
+        let registry = storage::get_repo_registry();
+        if issues
+            .iter()
+            .any(|i| registry.find(&i.repo_owner, &i.repo_name).is_none())
+        {
+            return bad_request_response();
+        }
+
+        let result = consensus::propose_sync_data(validator, &issues);
 
         if let Some(consensus_issues) = consensus::check_sync_consensus() {
-            storage::store_issue_data(&consensus_issues);
+            if storage::store_issue_data(&consensus_issues) {
+                scoring::rebuild_leaderboard();
+            }
         }
 
         ok_response(bincode::serialize(&result).unwrap_or_default())
@@ -259,6 +486,69 @@ pub fn handle_sync_propose(request: &WasmRouteRequest) -> WasmRouteResponse {
     }
 }
 
+pub fn handle_issue_content_propose(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    let validator = match validator_hotkey(request) {
+        Some(v) => v,
+        None => return forbidden_response(),
+    };
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+
+    match bincode_options_route_body().deserialize::<(String, Vec<IssueContent>)>(&request.body) {
+        Ok((validator_id, contents)) => {
+            if validator_id != validator {
+                return forbidden_response();
+            }
+            let registry = storage::get_repo_registry();
+            if contents
+                .iter()
+                .any(|c| registry.find(&c.repo_owner, &c.repo_name).is_none())
+            {
+                return bad_request_response();
+            }
+
+            let mut result = true;
+            for content in contents {
+                let content = quality::truncate_content(content);
+                result &= consensus::propose_issue_content(validator, &content);
+                if let Some(agreed) = consensus::check_content_consensus(
+                    &content.repo_owner,
+                    &content.repo_name,
+                    content.issue_number,
+                ) {
+                    storage::store_issue_content(&agreed);
+                }
+            }
+            ok_response(bincode::serialize(&result).unwrap_or_default())
+        }
+        Err(_) => bad_request_response(),
+    }
+}
+
+/// Runs the caller's LLM over part of the quality queue. Assessment happens
+/// here rather than during claims, so each call is bounded.
+pub fn handle_issues_assess(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    let validator = match validator_hotkey(request) {
+        Some(v) => v,
+        None => return forbidden_response(),
+    };
+
+    let config = storage::get_quality_config();
+    let assessed = if config.enabled {
+        quality::assess_queue(validator, &config)
+    } else {
+        0
+    };
+    ok_response(bincode::serialize(&assessed).unwrap_or_default())
+}
+
 pub fn handle_sync_consensus(_request: &WasmRouteRequest) -> WasmRouteResponse {
     let result = consensus::check_sync_consensus();
     ok_response(bincode::serialize(&result).unwrap_or_default())
@@ -304,6 +594,258 @@ pub fn handle_issue_consensus(request: &WasmRouteRequest) -> WasmRouteResponse {
     }
 }
 
+pub fn handle_star_propose(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    let validator = match validator_hotkey(request) {
+        Some(v) => v,
+        None => return forbidden_response(),
+    };
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+
+    if let Ok((validator_id, users)) =
+        bincode_options_route_body().deserialize::<(String, Vec<UserStars>)>(&request.body)
+    {
+        if validator_id != validator {
+            return forbidden_response();
+        }
+        let targets = storage::get_star_targets();
+        let users: Vec<UserStars> = users
+            .into_iter()
+            .map(|u| UserStars {
+                starred: storage::normalize_starred(&targets, &u.starred),
+                github_username: u.github_username,
+            })
+            .collect();
+        let result = consensus::propose_stars(validator, &users);
+
+        for user in &users {
+            let hotkey = match storage::get_hotkey_by_github(&user.github_username) {
+                Some(h) => h,
+                None => continue,
+            };
+            if let Some(agreed) = consensus::check_star_consensus(&user.github_username) {
+                if agreed != storage::get_verified_stars(&hotkey)
+                    && storage::set_verified_stars(&hotkey, &agreed, &targets)
+                {
+                    scoring::update_leaderboard_entry(&hotkey);
+                }
+            }
+        }
+
+        ok_response(bincode::serialize(&result).unwrap_or_default())
+    } else {
+        bad_request_response()
+    }
+}
+
+pub fn handle_get_repo_registry(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let registry = storage::get_repo_registry();
+    ok_response(bincode::serialize(&registry).unwrap_or_default())
+}
+
+pub fn handle_set_repo_registry(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    match bincode_options_route_body().deserialize::<RepositoryRegistry>(&request.body) {
+        Ok(registry) => {
+            if !validation::validate_repo_registry(&registry) {
+                return bad_request_response();
+            }
+            let result = storage::store_repo_registry(&registry);
+            ok_response(bincode::serialize(&result).unwrap_or_default())
+        }
+        Err(_) => bad_request_response(),
+    }
+}
+
+pub fn handle_get_validators(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let validators = storage::get_validator_set();
+    ok_response(bincode::serialize(&validators).unwrap_or_default())
+}
+
+pub fn handle_set_validators(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    match bincode_options_route_body().deserialize::<ValidatorSet>(&request.body) {
+        Ok(validators) => {
+            let unique: BTreeSet<&String> = validators.hotkeys.iter().collect();
+            if unique.len() != validators.hotkeys.len()
+                || validators.hotkeys.iter().any(|h| h.is_empty())
+            {
+                return bad_request_response();
+            }
+            let result = storage::store_validator_set(&validators);
+            ok_response(bincode::serialize(&result).unwrap_or_default())
+        }
+        Err(_) => bad_request_response(),
+    }
+}
+
+pub fn handle_get_star_targets(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let targets = storage::get_star_targets();
+    ok_response(bincode::serialize(&targets).unwrap_or_default())
+}
+
+pub fn handle_set_star_targets(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    if let Ok(targets) = bincode_options_route_body().deserialize::<StarTargets>(&request.body) {
+        let registry = storage::get_repo_registry();
+        if targets
+            .repos
+            .iter()
+            .any(|t| registry.find(&t.repo_owner, &t.repo_name).is_none())
+        {
+            return bad_request_response();
+        }
+
+        let result = storage::store_star_targets(&targets);
+        if result {
+            for hotkey in storage::get_registered_hotkeys() {
+                let starred = storage::get_verified_stars(&hotkey);
+                storage::set_verified_stars(&hotkey, &starred, &targets);
+            }
+            scoring::rebuild_leaderboard();
+        }
+        ok_response(bincode::serialize(&result).unwrap_or_default())
+    } else {
+        bad_request_response()
+    }
+}
+
+pub fn handle_get_claim_limits(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let limits = storage::get_claim_limits();
+    ok_response(bincode::serialize(&limits).unwrap_or_default())
+}
+
+pub fn handle_set_claim_limits(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    match bincode_options_route_body().deserialize::<ClaimLimits>(&request.body) {
+        Ok(limits) => {
+            if !validation::validate_claim_limits(&limits) {
+                return bad_request_response();
+            }
+            let result = storage::store_claim_limits(&limits);
+            ok_response(bincode::serialize(&result).unwrap_or_default())
+        }
+        Err(_) => bad_request_response(),
+    }
+}
+
+pub fn handle_get_duplicate_config(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let config = storage::get_duplicate_config();
+    ok_response(bincode::serialize(&config).unwrap_or_default())
+}
+
+pub fn handle_set_duplicate_config(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    match bincode_options_route_body().deserialize::<DuplicateConfig>(&request.body) {
+        Ok(config) => {
+            if !validation::validate_duplicate_config(&config) {
+                return bad_request_response();
+            }
+            let result = storage::store_duplicate_config(&config);
+            ok_response(bincode::serialize(&result).unwrap_or_default())
+        }
+        Err(_) => bad_request_response(),
+    }
+}
+
+pub fn handle_get_quality_config(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let config = storage::get_quality_config();
+    ok_response(bincode::serialize(&config).unwrap_or_default())
+}
+
+pub fn handle_set_quality_config(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    match bincode_options_route_body().deserialize::<QualityConfig>(&request.body) {
+        Ok(config) => {
+            if !quality::validate_quality_config(&config) {
+                return bad_request_response();
+            }
+            let result = storage::store_quality_config(&config);
+            ok_response(bincode::serialize(&result).unwrap_or_default())
+        }
+        Err(_) => bad_request_response(),
+    }
+}
+
+pub fn handle_get_auth_config(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let config = storage::get_auth_config();
+    ok_response(bincode::serialize(&config).unwrap_or_default())
+}
+
+pub fn handle_set_auth_config(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    match bincode_options_route_body().deserialize::<AuthConfig>(&request.body) {
+        Ok(config) => {
+            if !signature::validate_auth_config(&config) {
+                return bad_request_response();
+            }
+            let result = storage::store_auth_config(&config);
+            ok_response(bincode::serialize(&result).unwrap_or_default())
+        }
+        Err(_) => bad_request_response(),
+    }
+}
+
 pub fn handle_get_timeout_config(_request: &WasmRouteRequest) -> WasmRouteResponse {
     let config: TimeoutConfig =
         platform_challenge_sdk_wasm::host_functions::host_storage_get(b"timeout_config")
@@ -323,6 +865,9 @@ pub fn handle_set_timeout_config(request: &WasmRouteRequest) -> WasmRouteRespons
     if !is_authenticated(request) {
         return unauthorized_response();
     }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
     if request.body.len() > MAX_ROUTE_BODY_SIZE {
         return bad_request_response();
     }
@@ -339,8 +884,170 @@ pub fn handle_set_timeout_config(request: &WasmRouteRequest) -> WasmRouteRespons
     }
 }
 
+pub fn handle_get_scoring_config(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let config = storage::get_scoring_config();
+    ok_response(bincode::serialize(&config).unwrap_or_default())
+}
+
+pub fn handle_set_scoring_config(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    match bincode_options_route_body().deserialize::<ScoringConfig>(&request.body) {
+        Ok(config) => {
+            if !scoring::validate_scoring_config(&config) {
+                return bad_request_response();
+            }
+            let result = storage::store_scoring_config(&config);
+            if result {
+                scoring::rebuild_leaderboard();
+            }
+            ok_response(bincode::serialize(&result).unwrap_or_default())
+        }
+        Err(_) => bad_request_response(),
+    }
+}
+
+pub fn handle_get_decay_config(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let config = storage::get_decay_config();
+    ok_response(bincode::serialize(&config).unwrap_or_default())
+}
+
+pub fn handle_set_decay_config(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    if let Ok(config) = bincode_options_route_body().deserialize::<DecayConfig>(&request.body) {
+        let result = storage::store_decay_config(&config);
+        if result {
+            scoring::rebuild_leaderboard();
+        }
+        ok_response(bincode::serialize(&result).unwrap_or_default())
+    } else {
+        bad_request_response()
+    }
+}
+
+pub fn handle_get_point_multipliers(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let multipliers = storage::get_point_multipliers();
+    ok_response(bincode::serialize(&multipliers).unwrap_or_default())
+}
+
+pub fn handle_set_point_multipliers(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    if let Ok(multipliers) =
+        bincode_options_route_body().deserialize::<PointMultipliers>(&request.body)
+    {
+        if !scoring::validate_point_multipliers(&multipliers) {
+            return bad_request_response();
+        }
+        let result = storage::store_point_multipliers(&multipliers);
+        ok_response(bincode::serialize(&result).unwrap_or_default())
+    } else {
+        bad_request_response()
+    }
+}
+
+pub fn handle_get_label_tiers(_request: &WasmRouteRequest) -> WasmRouteResponse {
+    let tiers = storage::get_label_tiers();
+    ok_response(bincode::serialize(&tiers).unwrap_or_default())
+}
+
+pub fn handle_set_label_tiers(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if !is_authenticated(request) {
+        return unauthorized_response();
+    }
+    if !is_owner(request) {
+        return forbidden_response();
+    }
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    if let Ok(tiers) = bincode_options_route_body().deserialize::<LabelTiers>(&request.body) {
+        if !scoring::validate_label_tiers(&tiers) {
+            return bad_request_response();
+        }
+        let result = storage::store_label_tiers(&tiers);
+        ok_response(bincode::serialize(&result).unwrap_or_default())
+    } else {
+        bad_request_response()
+    }
+}
+
+pub fn handle_simulate(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    let simulation: SimulationRequest =
+        match bincode_options_route_body().deserialize(&request.body) {
+            Ok(s) => s,
+            Err(_) => return bad_request_response(),
+        };
+    if let Some(ref config) = simulation.scoring {
+        if !scoring::validate_scoring_config(config) {
+            return bad_request_response();
+        }
+    }
+    let simulated_issues = simulation
+        .adjustments
+        .iter()
+        .fold(0u32, |acc, a| acc.saturating_add(a.additional_valid_issues));
+    if simulated_issues > MAX_SIMULATED_ISSUES {
+        return bad_request_response();
+    }
+    let result = scoring::simulate(&simulation);
+    ok_response(bincode::serialize(&result).unwrap_or_default())
+}
+
 pub fn handle_get_weights(_request: &WasmRouteRequest) -> WasmRouteResponse {
-    let entries = storage::get_leaderboard();
-    let weights = scoring::calculate_weights_from_leaderboard(&entries);
-    ok_response(bincode::serialize(&weights).unwrap_or_default())
+    let config = storage::get_scoring_config();
+    let response = WeightsResponse {
+        weights: scoring::current_weights(&config),
+        max_weight_share: config.max_weight_share,
+        burn_hotkey: config.burn_hotkey,
+        burn_fraction: config.burn_fraction,
+        ema_alpha: config.ema_alpha,
+    };
+    ok_response(bincode::serialize(&response).unwrap_or_default())
+}
+
+pub fn handle_get_u16_weights(request: &WasmRouteRequest) -> WasmRouteResponse {
+    if request.body.len() > MAX_ROUTE_BODY_SIZE {
+        return bad_request_response();
+    }
+    let uids: Vec<UidMapping> = if request.body.is_empty() {
+        Vec::new()
+    } else {
+        match bincode_options_route_body().deserialize(&request.body) {
+            Ok(u) => u,
+            Err(_) => return bad_request_response(),
+        }
+    };
+    if !scoring::validate_uid_mapping(&uids) {
+        return bad_request_response();
+    }
+    let config = storage::get_scoring_config();
+    let weights = scoring::current_weights(&config);
+    let chain_weights = scoring::calculate_u16_weights(&weights, &uids);
+    ok_response(bincode::serialize(&chain_weights).unwrap_or_default())
 }
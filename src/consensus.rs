@@ -2,10 +2,35 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use platform_challenge_sdk_wasm::host_functions::{host_storage_get, host_storage_set};
 
-use crate::types::{IssueRecord, IssueValidityProposal};
+use crate::fingerprint::Fingerprint;
+use crate::storage;
+use crate::types::{
+    IssueContent, IssueRecord, IssueValidityProposal, QualityAssessment, QualityProposal,
+    StarProposal, StarTarget, UserStars,
+};
 
 const ISSUE_PROPOSALS_KEY: &[u8] = b"issue_validity_proposals";
 const SYNC_PROPOSALS_KEY: &[u8] = b"sync_proposals";
+const STAR_PROPOSALS_KEY: &[u8] = b"star_proposals";
+
+fn issue_proposals_key(
+    prefix: &[u8],
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u32,
+) -> Vec<u8> {
+    let mut key = Vec::from(prefix);
+    key.extend_from_slice(repo_owner.as_bytes());
+    key.push(b'/');
+    key.extend_from_slice(repo_name.as_bytes());
+    key.push(b':');
+    key.extend_from_slice(&issue_number.to_le_bytes());
+    key
+}
+
+fn content_proposals_key(repo_owner: &str, repo_name: &str, issue_number: u32) -> Vec<u8> {
+    issue_proposals_key(b"content_proposals:", repo_owner, repo_name, issue_number)
+}
 
 pub fn propose_issue_validity(
     validator_id: &str,
@@ -121,43 +146,268 @@ pub fn check_sync_consensus() -> Option<Vec<IssueRecord>> {
         })
         .unwrap_or_default();
 
+    let validators = storage::get_validator_set();
+    let proposals: Vec<&(String, Vec<IssueRecord>)> = proposals
+        .iter()
+        .filter(|(v, _)| validators.hotkeys.contains(v))
+        .collect();
     if proposals.is_empty() {
         return None;
     }
 
-    let validator_count = proposals.len();
-    let threshold = (validator_count / 2) + 1;
+    let threshold = (validators.hotkeys.len() / 2) + 1;
+
+    let keys: Vec<Vec<SyncedIssueKey<'_>>> = proposals
+        .iter()
+        .map(|(_, issues)| sync_key(issues))
+        .collect();
+    for (idx, key) in keys.iter().enumerate() {
+        if keys.iter().filter(|k| *k == key).count() >= threshold {
+            return Some(proposals[idx].1.clone());
+        }
+    }
+    None
+}
+
+/// The synced fields of an issue that claims and points depend on.
+/// Validators only agree on a sync when every issue matches on all of them,
+/// so no single proposal can set labels, timestamps or fingerprints alone.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct SyncedIssueKey<'a> {
+    repo_owner: &'a str,
+    repo_name: &'a str,
+    issue_number: u32,
+    author: &'a str,
+    is_closed: bool,
+    has_valid_label: bool,
+    has_invalid_label: bool,
+    labels: Vec<&'a str>,
+    created_at: Option<i64>,
+    closed_at: Option<i64>,
+    fingerprint: Option<Fingerprint>,
+}
 
-    let mut counts: Vec<(Vec<u32>, usize, usize)> = Vec::new();
-    for (_, issues) in &proposals {
-        let mut issue_nums: Vec<u32> = issues.iter().map(|i| i.issue_number).collect();
-        issue_nums.sort_unstable();
+fn sync_key(issues: &[IssueRecord]) -> Vec<SyncedIssueKey<'_>> {
+    let mut keys: Vec<SyncedIssueKey<'_>> = issues
+        .iter()
+        .map(|i| {
+            let mut labels: Vec<&str> = i.labels.iter().map(String::as_str).collect();
+            labels.sort_unstable();
+            SyncedIssueKey {
+                repo_owner: &i.repo_owner,
+                repo_name: &i.repo_name,
+                issue_number: i.issue_number,
+                author: &i.author,
+                is_closed: i.is_closed,
+                has_valid_label: i.has_valid_label,
+                has_invalid_label: i.has_invalid_label,
+                labels,
+                created_at: i.created_at,
+                closed_at: i.closed_at,
+                fingerprint: i.fingerprint,
+            }
+        })
+        .collect();
+    keys.sort_unstable();
+    keys
+}
 
-        if let Some(entry) = counts.iter_mut().find(|(k, _, _)| *k == issue_nums) {
-            entry.1 += 1;
+fn get_content_proposals(key: &[u8]) -> Vec<(String, IssueContent)> {
+    host_storage_get(key)
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Records one validator's text for an issue, replacing its earlier one.
+/// Proposals are kept per issue so each stays small.
+pub fn propose_issue_content(validator_id: &str, content: &IssueContent) -> bool {
+    let key = content_proposals_key(
+        &content.repo_owner,
+        &content.repo_name,
+        content.issue_number,
+    );
+    let mut proposals = get_content_proposals(&key);
+
+    if let Some(pos) = proposals.iter().position(|(v, _)| v == validator_id) {
+        proposals[pos].1 = content.clone();
+    } else {
+        proposals.push((String::from(validator_id), content.clone()));
+    }
+
+    if let Ok(data) = bincode::serialize(&proposals) {
+        return host_storage_set(&key, &data).is_ok();
+    }
+    false
+}
+
+/// Returns the text a strict majority of the validator set proposed for the
+/// issue.
+pub fn check_content_consensus(
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u32,
+) -> Option<IssueContent> {
+    let validators = storage::get_validator_set();
+    let proposals =
+        get_content_proposals(&content_proposals_key(repo_owner, repo_name, issue_number));
+    let relevant: Vec<&IssueContent> = proposals
+        .iter()
+        .filter(|(v, _)| validators.hotkeys.contains(v))
+        .map(|(_, c)| c)
+        .collect();
+
+    let threshold = (validators.hotkeys.len() / 2) + 1;
+    relevant
+        .iter()
+        .find(|candidate| relevant.iter().filter(|c| c == candidate).count() >= threshold)
+        .map(|c| (*c).clone())
+}
+
+/// Assessments of an issue proposed by validators still in the validator set.
+pub fn get_quality_proposals(
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u32,
+) -> Vec<QualityProposal> {
+    let validators = storage::get_validator_set();
+    let key = issue_proposals_key(b"quality_proposals:", repo_owner, repo_name, issue_number);
+    host_storage_get(&key)
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize::<Vec<QualityProposal>>(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|p| validators.hotkeys.contains(&p.validator_id))
+        .collect()
+}
+
+/// Stores `proposals` as the assessments of an issue, or removes them all
+/// when empty.
+pub fn store_quality_proposals(
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u32,
+    proposals: &[QualityProposal],
+) -> bool {
+    let key = issue_proposals_key(b"quality_proposals:", repo_owner, repo_name, issue_number);
+    if proposals.is_empty() {
+        return host_storage_set(&key, &[]).is_ok();
+    }
+    if let Ok(data) = bincode::serialize(proposals) {
+        return host_storage_set(&key, &data).is_ok();
+    }
+    false
+}
+
+/// Records `validator_id`'s assessment of an issue, replacing its earlier one.
+pub fn propose_quality(
+    validator_id: &str,
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u32,
+    assessment: Option<QualityAssessment>,
+) -> bool {
+    let mut proposals = get_quality_proposals(repo_owner, repo_name, issue_number);
+    if let Some(pos) = proposals
+        .iter()
+        .position(|p| p.validator_id == validator_id)
+    {
+        proposals[pos].assessment = assessment;
+    } else {
+        proposals.push(QualityProposal {
+            validator_id: String::from(validator_id),
+            assessment,
+        });
+    }
+    store_quality_proposals(repo_owner, repo_name, issue_number, &proposals)
+}
+
+fn get_star_proposals() -> Vec<StarProposal> {
+    host_storage_get(STAR_PROPOSALS_KEY)
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Records one validator's star observations. Each `starred` list must
+/// already be normalized so equal observations compare equal.
+pub fn propose_stars(validator_id: &str, users: &[UserStars]) -> bool {
+    let mut proposals = get_star_proposals();
+
+    for user in users {
+        if let Some(pos) = proposals.iter().position(|p| {
+            p.validator_id == validator_id
+                && p.github_username
+                    .eq_ignore_ascii_case(&user.github_username)
+        }) {
+            proposals[pos].starred = user.starred.clone();
         } else {
-            let idx = proposals
-                .iter()
-                .position(|(_, pi)| {
-                    let mut nums: Vec<u32> = pi.iter().map(|i| i.issue_number).collect();
-                    nums.sort_unstable();
-                    nums == issue_nums
-                })
-                .unwrap_or(0);
-            counts.push((issue_nums, 1, idx));
+            proposals.push(StarProposal {
+                validator_id: String::from(validator_id),
+                github_username: user.github_username.to_lowercase(),
+                starred: user.starred.clone(),
+            });
         }
     }
 
-    for (_, count, idx) in &counts {
-        if *count >= threshold {
-            return Some(proposals[*idx].1.clone());
-        }
+    if let Ok(data) = bincode::serialize(&proposals) {
+        return host_storage_set(STAR_PROPOSALS_KEY, &data).is_ok();
     }
-    None
+    false
+}
+
+/// Returns the starred set a strict majority of registered validators agree
+/// on for `github_username`. Proposals from hotkeys no longer in the
+/// validator set are ignored.
+pub fn check_star_consensus(github_username: &str) -> Option<Vec<StarTarget>> {
+    let validators = storage::get_validator_set();
+    let proposals = get_star_proposals();
+    let relevant: Vec<&StarProposal> = proposals
+        .iter()
+        .filter(|p| {
+            p.github_username.eq_ignore_ascii_case(github_username)
+                && validators.hotkeys.contains(&p.validator_id)
+        })
+        .collect();
+
+    if relevant.is_empty() {
+        return None;
+    }
+
+    let threshold = (storage::get_validator_count() as usize / 2) + 1;
+    relevant
+        .iter()
+        .find(|candidate| {
+            relevant
+                .iter()
+                .filter(|p| p.starred == candidate.starred)
+                .count()
+                >= threshold
+        })
+        .map(|p| p.starred.clone())
 }
 
 #[allow(dead_code)]
 pub fn clear_proposals() {
     let _ = host_storage_set(ISSUE_PROPOSALS_KEY, &[]);
     let _ = host_storage_set(SYNC_PROPOSALS_KEY, &[]);
+    let _ = host_storage_set(STAR_PROPOSALS_KEY, &[]);
 }
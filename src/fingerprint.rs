@@ -0,0 +1,90 @@
+//! Content fingerprints for near-duplicate detection. Validators compute
+//! `issue_fingerprint` over an issue's title and body during sync, so two
+//! reports of the same bug with light rewording land a few bits apart.
+
+pub type Fingerprint = u64;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// 64-bit simhash over the lowercase alphanumeric words of `text`, each
+/// hashed with FNV-1a. Case, punctuation and whitespace do not affect the
+/// result.
+pub fn simhash(text: &str) -> Fingerprint {
+    let mut counts = [0i64; 64];
+    let words = text
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty());
+    for word in words {
+        let mut lower = [0u8; 4];
+        let mut hash = FNV_OFFSET;
+        for c in word.chars().flat_map(char::to_lowercase) {
+            for &b in c.encode_utf8(&mut lower).as_bytes() {
+                hash = (hash ^ b as u64).wrapping_mul(FNV_PRIME);
+            }
+        }
+        for (bit, count) in counts.iter_mut().enumerate() {
+            if hash >> bit & 1 == 1 {
+                *count += 1;
+            } else {
+                *count -= 1;
+            }
+        }
+    }
+
+    counts
+        .iter()
+        .enumerate()
+        .filter(|(_, &count)| count > 0)
+        .fold(0, |fingerprint, (bit, _)| fingerprint | 1 << bit)
+}
+
+pub fn issue_fingerprint(title: &str, body: &str) -> Fingerprint {
+    simhash(&alloc::format!("{} {}", title, body))
+}
+
+pub fn hamming_distance(a: Fingerprint, b: Fingerprint) -> u32 {
+    (a ^ b).count_ones()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DuplicateConfig;
+
+    const REPORT: &str = "Crash when saving a project with unicode file names. Steps to \
+        reproduce: create a new project, add a file named with accented characters, \
+        press save. Expected the project to be written to disk, but the editor panics \
+        with an invalid utf8 error and the unsaved changes are lost.";
+
+    #[test]
+    fn identical_text_has_zero_distance() {
+        assert_eq!(hamming_distance(simhash(REPORT), simhash(REPORT)), 0);
+        assert_eq!(
+            simhash("Crash on SAVE, with unicode!"),
+            simhash("crash on save with   unicode")
+        );
+    }
+
+    #[test]
+    fn near_duplicates_fall_within_threshold() {
+        let reworded = REPORT.replace("Expected", "I expected");
+        let distance = hamming_distance(simhash(REPORT), simhash(&reworded));
+        assert!(
+            distance <= DuplicateConfig::default().max_distance,
+            "{distance}"
+        );
+    }
+
+    #[test]
+    fn unrelated_text_falls_outside_threshold() {
+        let other = "Add a dark theme to the settings page. The current light colors are \
+            hard on the eyes at night, and most other tools already offer a theme switch \
+            next to the font size option.";
+        let distance = hamming_distance(simhash(REPORT), simhash(other));
+        assert!(
+            distance > DuplicateConfig::default().max_distance,
+            "{distance}"
+        );
+    }
+}
@@ -0,0 +1,90 @@
+//! Fixed-point arithmetic in parts per billion. Every operation works on
+//! integers and rounds toward zero, so scores and weights are bit-identical
+//! on every validator regardless of platform or summation order.
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+pub type Ppb = u64;
+
+pub const SCALE: Ppb = 1_000_000_000;
+
+pub const fn from_int(n: u64) -> Ppb {
+    n.saturating_mul(SCALE)
+}
+
+pub fn mul(a: Ppb, b: Ppb) -> Ppb {
+    let product = a as u128 * b as u128 / SCALE as u128;
+    product.min(u64::MAX as u128) as u64
+}
+
+pub fn mul_int(a: Ppb, n: u64) -> Ppb {
+    a.saturating_mul(n)
+}
+
+/// Splits `total` across `shares` in proportion to their values using the
+/// largest-remainder method. Each share is floored first and the leftover
+/// units go to the largest remainders, ties broken by ascending key. The
+/// result sums to exactly `total` unless every share is zero.
+pub fn apportion<K: Ord>(shares: &[(K, u64)], total: u64) -> Vec<u64> {
+    let sum: u128 = shares.iter().map(|(_, s)| *s as u128).sum();
+    if sum == 0 {
+        return vec![0; shares.len()];
+    }
+
+    let mut result = Vec::with_capacity(shares.len());
+    let mut remainders = Vec::with_capacity(shares.len());
+    for (i, (_, share)) in shares.iter().enumerate() {
+        let scaled = *share as u128 * total as u128;
+        result.push((scaled / sum) as u64);
+        remainders.push((i, scaled % sum));
+    }
+
+    let assigned: u128 = result.iter().map(|r| *r as u128).sum();
+    let mut leftover = total as u128 - assigned;
+    remainders.sort_by(|a, b| {
+        b.1.cmp(&a.1)
+            .then_with(|| shares[a.0].0.cmp(&shares[b.0].0))
+    });
+    for (i, _) in remainders {
+        if leftover == 0 {
+            break;
+        }
+        result[i] += 1;
+        leftover -= 1;
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apportion_sums_to_total() {
+        let shares = [("a", 7), ("b", 13), ("c", 29), ("d", 1)];
+        let result = apportion(&shares, SCALE);
+        assert_eq!(result.iter().sum::<u64>(), SCALE);
+        assert_eq!(apportion(&[("a", 3), ("b", 1)], 1000), [750, 250]);
+    }
+
+    #[test]
+    fn apportion_breaks_ties_by_key() {
+        assert_eq!(
+            apportion(&[("a", 1), ("b", 1), ("c", 1)], 100),
+            [34, 33, 33]
+        );
+        assert_eq!(
+            apportion(&[("c", 1), ("b", 1), ("a", 1)], 100),
+            [33, 33, 34]
+        );
+        assert_eq!(apportion(&[("b", 1), ("a", 1)], 3), [1, 2]);
+    }
+
+    #[test]
+    fn apportion_of_zero_shares_is_zero() {
+        assert_eq!(apportion(&[("a", 0), ("b", 0)], 100), [0, 0]);
+        assert!(apportion::<&str>(&[], 100).is_empty());
+    }
+}
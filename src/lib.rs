@@ -4,8 +4,12 @@ extern crate alloc;
 
 mod api;
 mod consensus;
+pub mod fingerprint;
+pub mod fixed;
+mod quality;
 mod routes;
 mod scoring;
+mod signature;
 pub mod storage;
 pub mod types;
 mod validation;
@@ -13,21 +17,14 @@ mod validation;
 use alloc::string::String;
 use alloc::vec::Vec;
 use bincode::Options;
-use platform_challenge_sdk_wasm::host_functions::host_storage_get;
 use platform_challenge_sdk_wasm::{Challenge, EvaluationInput, EvaluationOutput, WasmRouteRequest};
 
-use crate::types::{BountySubmission, LeaderboardEntry};
+use crate::types::RejectionCode;
+use crate::validation::ClaimPayload;
 
 const MAX_SUBMISSION_SIZE: u64 = 4 * 1024 * 1024;
 const MAX_ROUTE_REQUEST_SIZE: u64 = 1024 * 1024;
 
-fn bincode_options_submission() -> impl Options {
-    bincode::DefaultOptions::new()
-        .with_limit(MAX_SUBMISSION_SIZE)
-        .with_fixint_encoding()
-        .allow_trailing_bytes()
-}
-
 fn bincode_options_route_request() -> impl Options {
     bincode::DefaultOptions::new()
         .with_limit(MAX_ROUTE_REQUEST_SIZE)
@@ -59,11 +56,12 @@ impl Challenge for BountyChallengeWasm {
     }
 
     fn evaluate(&self, input: EvaluationInput) -> EvaluationOutput {
-        let submission: BountySubmission =
-            match bincode_options_submission().deserialize(&input.agent_data) {
-                Ok(s) => s,
-                Err(_) => return EvaluationOutput::failure("failed to deserialize submission"),
-            };
+        let payload = match ClaimPayload::decode(&input.agent_data, MAX_SUBMISSION_SIZE) {
+            Some(p) => p,
+            None => return EvaluationOutput::failure("failed to deserialize submission"),
+        };
+        let verified = payload.verify_signature();
+        let submission = payload.into_submission();
 
         if submission.hotkey.is_empty() {
             return EvaluationOutput::failure("missing hotkey");
@@ -73,7 +71,7 @@ impl Challenge for BountyChallengeWasm {
             return EvaluationOutput::failure("missing github_username");
         }
 
-        if submission.issue_numbers.is_empty() {
+        if submission.targets.is_empty() {
             return EvaluationOutput::failure("no issues to claim");
         }
 
@@ -81,6 +79,15 @@ impl Challenge for BountyChallengeWasm {
             return EvaluationOutput::failure("missing signature");
         }
 
+        if !verified {
+            return EvaluationOutput::failure("invalid signature");
+        }
+
+        let auth_config = storage::get_auth_config();
+        if !signature::is_timestamp_fresh(submission.timestamp, &auth_config) {
+            return EvaluationOutput::failure("timestamp outside allowed window");
+        }
+
         let reg = match storage::get_user_by_hotkey(&submission.hotkey) {
             Some(r) => r,
             None => return EvaluationOutput::failure("hotkey not registered"),
@@ -90,14 +97,33 @@ impl Challenge for BountyChallengeWasm {
             return EvaluationOutput::failure("github username mismatch with registration");
         }
 
+        if !signature::consume_signature(
+            &submission.hotkey,
+            submission.timestamp,
+            &submission.signature,
+            &auth_config,
+        ) {
+            return EvaluationOutput::failure("signature already used");
+        }
+
+        let limits = storage::get_claim_limits();
+        let epoch = scoring::current_epoch();
+        if let Err(code) = validation::check_claim_limits(&submission, &limits, epoch) {
+            return EvaluationOutput::failure(match code {
+                RejectionCode::TooManyIssues => "too many issues in submission",
+                RejectionCode::CoolingDown => "claims paused after repeated rejections",
+                _ => "submission limit for this epoch reached",
+            });
+        }
+
         let synced_issues = storage::get_synced_issues();
         let result = validation::process_claims(&submission, &synced_issues);
 
         if !result.claimed.is_empty() {
-            scoring::rebuild_leaderboard();
+            scoring::update_leaderboard_entry(&submission.hotkey);
         }
 
-        let score = (result.score * 10_000.0) as i64;
+        let score = (result.score / 100_000) as i64;
 
         let mut message = String::from("claimed=");
         let claimed_count = result.claimed.len();
@@ -105,8 +131,12 @@ impl Challenge for BountyChallengeWasm {
         let _ = core::fmt::Write::write_fmt(
             &mut message,
             format_args!(
-                "{} rejected={} total_valid={} weight={:.4}",
-                claimed_count, rejected_count, result.total_valid, result.score
+                "{} rejected={} total_valid={} weight={}.{:04}",
+                claimed_count,
+                rejected_count,
+                result.total_valid,
+                result.score / fixed::SCALE,
+                result.score % fixed::SCALE / 100_000
             ),
         );
 
@@ -114,13 +144,13 @@ impl Challenge for BountyChallengeWasm {
     }
 
     fn validate(&self, input: EvaluationInput) -> bool {
-        let submission: BountySubmission =
-            match bincode_options_submission().deserialize(&input.agent_data) {
-                Ok(s) => s,
-                Err(_) => return false,
-            };
-
-        validation::validate_submission(&submission)
+        match ClaimPayload::decode(&input.agent_data, MAX_SUBMISSION_SIZE) {
+            Some(payload) => {
+                payload.verify_signature()
+                    && validation::validate_submission(&payload.into_submission())
+            }
+            None => false,
+        }
     }
 
     fn routes(&self) -> Vec<u8> {
@@ -139,19 +169,9 @@ impl Challenge for BountyChallengeWasm {
     }
 
     fn get_weights(&self) -> Vec<u8> {
-        let entries: Vec<LeaderboardEntry> = host_storage_get(b"leaderboard")
-            .ok()
-            .and_then(|d| {
-                if d.is_empty() {
-                    None
-                } else {
-                    bincode::deserialize(&d).ok()
-                }
-            })
-            .unwrap_or_default();
-
-        let weights = scoring::calculate_weights_from_leaderboard(&entries);
-        bincode::serialize(&weights).unwrap_or_default()
+        let config = storage::get_scoring_config();
+        let weights = scoring::current_weights(&config);
+        bincode::serialize(&scoring::to_host_weights(&weights)).unwrap_or_default()
     }
 }
 
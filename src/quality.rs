@@ -0,0 +1,384 @@
+//! Optional LLM quality assessment of claimed issues. Credited issues are
+//! queued, and validators assess them outside the claim path: the issue
+//! title and body, stored apart from the synced data as `IssueContent`, are
+//! sent as an `LlmRequest` asking for a `SCORE:` and `VERDICT:` line, which
+//! are parsed from the `LlmResponse` into a `QualityAssessment`. Each
+//! validator's result is a proposal, and an issue is only flagged for
+//! review once a majority of the validator set agrees. Assessments never
+//! change points.
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use platform_challenge_sdk_wasm::host_functions::host_llm_chat_completion;
+
+use crate::fixed::{Ppb, SCALE};
+use crate::types::{
+    IssueContent, LlmMessage, LlmRequest, LlmResponse, QualityAssessment, QualityConfig,
+    QualityProposal, QualityReview, QualityVerdict,
+};
+use crate::{consensus, storage};
+
+const MAX_TITLE_CHARS: usize = 256;
+const MAX_BODY_CHARS: usize = 4000;
+const MAX_SCORE: u32 = 100;
+const MAX_TEMPERATURE: Ppb = 2 * SCALE;
+const MAX_ASSESSMENTS_PER_CALL: usize = 10;
+
+const SYSTEM_PROMPT: &str = "You review GitHub issues submitted for a bug bounty. \
+Judge whether the issue is a clear, reproducible and actionable report. \
+Reply with exactly two lines:\n\
+SCORE: <integer from 0 to 100>\n\
+VERDICT: <accept|review|reject>";
+
+pub trait LlmBackend {
+    fn complete(&self, request: &LlmRequest) -> Option<LlmResponse>;
+}
+
+/// Sends requests to the validator's LLM through the host.
+pub struct HostLlm;
+
+impl LlmBackend for HostLlm {
+    fn complete(&self, request: &LlmRequest) -> Option<LlmResponse> {
+        let data = bincode::serialize(request).ok()?;
+        let response = host_llm_chat_completion(&data).ok()?;
+        bincode::deserialize(&response).ok()
+    }
+}
+
+/// Cuts the title and body down to what is sent to the LLM, before storing.
+pub fn truncate_content(mut content: IssueContent) -> IssueContent {
+    content.title = content.title.chars().take(MAX_TITLE_CHARS).collect();
+    content.body = content.body.chars().take(MAX_BODY_CHARS).collect();
+    content
+}
+
+pub fn build_request(issue: &IssueContent, config: &QualityConfig) -> LlmRequest {
+    LlmRequest {
+        model: config.model.clone(),
+        messages: vec![
+            LlmMessage {
+                role: String::from("system"),
+                content: String::from(SYSTEM_PROMPT),
+            },
+            LlmMessage {
+                role: String::from("user"),
+                content: alloc::format!(
+                    "Repository: {}/{}\nTitle: {}\n\n{}",
+                    issue.repo_owner,
+                    issue.repo_name,
+                    issue.title,
+                    issue.body
+                ),
+            },
+        ],
+        max_tokens: config.max_tokens,
+        temperature: config.temperature as f32 / SCALE as f32,
+    }
+}
+
+/// Reads the `SCORE:` and `VERDICT:` lines from `content`. Keys are matched
+/// case-insensitively and markdown emphasis around them is ignored; both
+/// lines are required.
+pub fn parse_response(content: &str) -> Option<QualityAssessment> {
+    let mut score = None;
+    let mut verdict = None;
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let key = key.trim_matches(|c: char| c == '*' || c == '-' || c.is_whitespace());
+        let value = value.trim_matches(|c: char| c == '*' || c == '.' || c.is_whitespace());
+        if key.eq_ignore_ascii_case("score") {
+            score = value.parse::<u32>().ok().filter(|s| *s <= MAX_SCORE);
+        } else if key.eq_ignore_ascii_case("verdict") {
+            verdict = match value.to_ascii_lowercase().as_str() {
+                "accept" => Some(QualityVerdict::Accept),
+                "review" => Some(QualityVerdict::Review),
+                "reject" => Some(QualityVerdict::Reject),
+                _ => None,
+            };
+        }
+    }
+    Some(QualityAssessment {
+        score: score?,
+        verdict: verdict?,
+    })
+}
+
+pub fn assess_with(
+    backend: &impl LlmBackend,
+    issue: &IssueContent,
+    config: &QualityConfig,
+) -> Option<QualityAssessment> {
+    let response = backend.complete(&build_request(issue, config))?;
+    parse_response(&response.content)
+}
+
+/// Assesses `issue` with the validator's LLM, or returns `None` when the
+/// stage is disabled.
+pub fn assess(issue: &IssueContent, config: &QualityConfig) -> Option<QualityAssessment> {
+    if !config.enabled {
+        return None;
+    }
+    assess_with(&HostLlm, issue, config)
+}
+
+/// Where the validator set stands on one queued issue.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Decision {
+    /// A majority flagged it; reviewed with the median flagging assessment.
+    Flag(QualityAssessment),
+    /// A majority cleared it, or every validator answered without a majority.
+    Clear,
+    Pending,
+}
+
+pub fn decide(
+    proposals: &[QualityProposal],
+    validator_count: usize,
+    config: &QualityConfig,
+) -> Decision {
+    let threshold = (validator_count / 2) + 1;
+    let mut flagged: Vec<QualityAssessment> = proposals
+        .iter()
+        .filter_map(|p| p.assessment)
+        .filter(|a| needs_review(a, config))
+        .collect();
+    let cleared = proposals
+        .iter()
+        .filter_map(|p| p.assessment)
+        .filter(|a| !needs_review(a, config))
+        .count();
+
+    if flagged.len() >= threshold {
+        flagged.sort_by_key(|a| a.score);
+        Decision::Flag(flagged[(flagged.len() - 1) / 2])
+    } else if cleared >= threshold || proposals.len() >= validator_count {
+        Decision::Clear
+    } else {
+        Decision::Pending
+    }
+}
+
+/// Assesses up to `MAX_ASSESSMENTS_PER_CALL` queued issues that `validator`
+/// has not assessed yet and whose text has been agreed, records each result
+/// as its proposal, and removes decided issues from the queue, adding
+/// flagged ones to the reviews. Returns the number assessed.
+pub fn assess_queue(validator: &str, config: &QualityConfig) -> u32 {
+    let validator_count = storage::get_validator_count() as usize;
+    let mut queue = storage::get_quality_queue();
+    let mut assessed = 0;
+
+    queue.retain(|pending| {
+        let (owner, name, number) = (
+            pending.repo_owner.as_str(),
+            pending.repo_name.as_str(),
+            pending.issue_number,
+        );
+        let mut proposals = consensus::get_quality_proposals(owner, name, number);
+        if assessed < MAX_ASSESSMENTS_PER_CALL
+            && !proposals.iter().any(|p| p.validator_id == validator)
+        {
+            if let Some(content) = storage::get_issue_content(owner, name, number) {
+                let assessment = assess(&content, config);
+                consensus::propose_quality(validator, owner, name, number, assessment);
+                proposals.push(QualityProposal {
+                    validator_id: String::from(validator),
+                    assessment,
+                });
+                assessed += 1;
+            }
+        }
+
+        match decide(&proposals, validator_count, config) {
+            Decision::Pending => true,
+            decision => {
+                if let Decision::Flag(assessment) = decision {
+                    storage::add_quality_review(QualityReview {
+                        repo_owner: pending.repo_owner.clone(),
+                        repo_name: pending.repo_name.clone(),
+                        issue_number: number,
+                        hotkey: pending.hotkey.clone(),
+                        assessment,
+                        points: pending.points,
+                        epoch: pending.epoch,
+                    });
+                }
+                consensus::store_quality_proposals(owner, name, number, &[]);
+                false
+            }
+        }
+    });
+
+    storage::store_quality_queue(&queue);
+    assessed as u32
+}
+
+pub fn needs_review(assessment: &QualityAssessment, config: &QualityConfig) -> bool {
+    assessment.score < config.review_below || assessment.verdict != QualityVerdict::Accept
+}
+
+pub fn validate_quality_config(config: &QualityConfig) -> bool {
+    config.max_tokens > 0
+        && config.temperature <= MAX_TEMPERATURE
+        && config.review_below <= MAX_SCORE
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Answers every request with the same content.
+    struct CannedLlm<'a>(&'a str);
+
+    impl LlmBackend for CannedLlm<'_> {
+        fn complete(&self, _request: &LlmRequest) -> Option<LlmResponse> {
+            Some(LlmResponse {
+                content: String::from(self.0),
+            })
+        }
+    }
+
+    fn issue(body: &str) -> IssueContent {
+        IssueContent {
+            repo_owner: String::from("PlatformNetwork"),
+            repo_name: String::from("bounty-challenge"),
+            issue_number: 42,
+            title: String::from("Crash on empty config"),
+            body: String::from(body),
+        }
+    }
+
+    fn assessment(score: u32, verdict: QualityVerdict) -> QualityAssessment {
+        QualityAssessment { score, verdict }
+    }
+
+    #[test]
+    fn parses_plain_response() {
+        assert_eq!(
+            parse_response("SCORE: 80\nVERDICT: accept"),
+            Some(assessment(80, QualityVerdict::Accept))
+        );
+    }
+
+    #[test]
+    fn parses_emphasis_and_case() {
+        let content = "Here is my review.\n**Score:** 35\n- verdict: **REJECT**.";
+        assert_eq!(
+            parse_response(content),
+            Some(assessment(35, QualityVerdict::Reject))
+        );
+    }
+
+    #[test]
+    fn rejects_missing_lines() {
+        assert_eq!(parse_response("SCORE: 80"), None);
+        assert_eq!(parse_response("VERDICT: review"), None);
+        assert_eq!(parse_response("Looks good to me."), None);
+    }
+
+    #[test]
+    fn rejects_invalid_values() {
+        assert_eq!(parse_response("SCORE: 101\nVERDICT: accept"), None);
+        assert_eq!(parse_response("SCORE: -5\nVERDICT: accept"), None);
+        assert_eq!(parse_response("SCORE: 80\nVERDICT: maybe"), None);
+    }
+
+    #[test]
+    fn flags_low_scores_and_other_verdicts() {
+        let config = QualityConfig::default();
+        assert!(!needs_review(
+            &assessment(50, QualityVerdict::Accept),
+            &config
+        ));
+        assert!(needs_review(
+            &assessment(49, QualityVerdict::Accept),
+            &config
+        ));
+        assert!(needs_review(
+            &assessment(90, QualityVerdict::Review),
+            &config
+        ));
+        assert!(needs_review(
+            &assessment(90, QualityVerdict::Reject),
+            &config
+        ));
+    }
+
+    fn proposal(validator: &str, assessment: Option<QualityAssessment>) -> QualityProposal {
+        QualityProposal {
+            validator_id: String::from(validator),
+            assessment,
+        }
+    }
+
+    #[test]
+    fn flags_with_median_of_majority() {
+        let config = QualityConfig::default();
+        let proposals = [
+            proposal("a", Some(assessment(40, QualityVerdict::Accept))),
+            proposal("b", Some(assessment(90, QualityVerdict::Accept))),
+            proposal("c", Some(assessment(20, QualityVerdict::Review))),
+        ];
+        assert_eq!(
+            decide(&proposals, 3, &config),
+            Decision::Flag(assessment(20, QualityVerdict::Review))
+        );
+        assert_eq!(decide(&proposals[..1], 3, &config), Decision::Pending);
+    }
+
+    #[test]
+    fn clears_with_majority_or_when_everyone_answered() {
+        let config = QualityConfig::default();
+        let cleared = [
+            proposal("a", Some(assessment(80, QualityVerdict::Accept))),
+            proposal("b", Some(assessment(70, QualityVerdict::Accept))),
+        ];
+        assert_eq!(decide(&cleared, 3, &config), Decision::Clear);
+
+        let split = [
+            proposal("a", Some(assessment(80, QualityVerdict::Accept))),
+            proposal("b", Some(assessment(10, QualityVerdict::Reject))),
+            proposal("c", None),
+        ];
+        assert_eq!(decide(&split[..2], 3, &config), Decision::Pending);
+        assert_eq!(decide(&split, 3, &config), Decision::Clear);
+    }
+
+    #[test]
+    fn assesses_with_backend_reply() {
+        let config = QualityConfig::default();
+        let llm = CannedLlm("SCORE: 72\nVERDICT: review");
+        assert_eq!(
+            assess_with(&llm, &issue("Steps to reproduce"), &config),
+            Some(assessment(72, QualityVerdict::Review))
+        );
+        assert_eq!(assess_with(&CannedLlm("n/a"), &issue(""), &config), None);
+    }
+
+    #[test]
+    fn truncates_stored_content() {
+        let mut long = issue(&"x".repeat(MAX_BODY_CHARS + 10));
+        long.title = "t".repeat(MAX_TITLE_CHARS + 10);
+        let content = truncate_content(long);
+        assert_eq!(content.title.chars().count(), MAX_TITLE_CHARS);
+        assert_eq!(content.body.chars().count(), MAX_BODY_CHARS);
+
+        let request = build_request(&issue("Steps to reproduce"), &QualityConfig::default());
+        assert!(request.messages[1]
+            .content
+            .contains("Title: Crash on empty config\n\nSteps to reproduce"));
+    }
+
+    #[test]
+    fn validates_config_bounds() {
+        let mut config = QualityConfig::default();
+        assert!(validate_quality_config(&config));
+        config.review_below = MAX_SCORE + 1;
+        assert!(!validate_quality_config(&config));
+        config.review_below = 50;
+        config.temperature = MAX_TEMPERATURE + 1;
+        assert!(!validate_quality_config(&config));
+    }
+}
@@ -13,6 +13,26 @@ pub fn get_route_definitions() -> Vec<WasmRouteDefinition> {
             description: String::from("Returns current leaderboard with scores and rankings"),
             requires_auth: false,
         },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/leaderboard/history"),
+            description: String::from(
+                "Leaderboard snapshot for an epoch (?epoch=N) or the list of snapshot epochs",
+            ),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/leaderboard/history/:hotkey"),
+            description: String::from("Rank history across epochs for a specific hotkey"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/leaderboard/rebuild"),
+            description: String::from("Rebuilds the full leaderboard from storage (requires auth)"),
+            requires_auth: true,
+        },
         WasmRouteDefinition {
             method: String::from("GET"),
             path: String::from("/stats"),
@@ -37,6 +57,14 @@ pub fn get_route_definitions() -> Vec<WasmRouteDefinition> {
             description: String::from("Claim bounty for resolved issues (requires auth)"),
             requires_auth: true,
         },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/claim/preview"),
+            description: String::from(
+                "Check a claim without recording it and project the resulting weight (requires auth)",
+            ),
+            requires_auth: true,
+        },
         WasmRouteDefinition {
             method: String::from("GET"),
             path: String::from("/issues"),
@@ -49,6 +77,36 @@ pub fn get_route_definitions() -> Vec<WasmRouteDefinition> {
             description: String::from("List pending issues"),
             requires_auth: false,
         },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/issues/duplicates"),
+            description: String::from(
+                "List claimed issues linked to the issue they near-duplicate",
+            ),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/issues/reviews"),
+            description: String::from("List claimed issues flagged by quality assessment"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/issues/assess"),
+            description: String::from(
+                "Assess queued issues with the caller's LLM (requires validator)",
+            ),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/issues/content/propose"),
+            description: String::from(
+                "Propose issue text for quality assessment (requires validator)",
+            ),
+            requires_auth: true,
+        },
         WasmRouteDefinition {
             method: String::from("GET"),
             path: String::from("/hotkey/:hotkey"),
@@ -64,7 +122,7 @@ pub fn get_route_definitions() -> Vec<WasmRouteDefinition> {
         WasmRouteDefinition {
             method: String::from("POST"),
             path: String::from("/sync/propose"),
-            description: String::from("Propose synced issue data for consensus (requires auth)"),
+            description: String::from("Propose synced issue data for consensus (requires validator)"),
             requires_auth: true,
         },
         WasmRouteDefinition {
@@ -85,6 +143,100 @@ pub fn get_route_definitions() -> Vec<WasmRouteDefinition> {
             description: String::from("Check issue validity consensus"),
             requires_auth: false,
         },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/repos"),
+            description: String::from("Returns repositories that are part of the bounty program"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/repos"),
+            description: String::from("Updates the bounty repository registry (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/stars/propose"),
+            description: String::from(
+                "Propose which star targets registered users have starred (requires validator)",
+            ),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/validators"),
+            description: String::from("Returns the validators whose proposals count"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/validators"),
+            description: String::from("Updates the validator set (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/stars/targets"),
+            description: String::from("Returns repositories eligible for the star bonus"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/stars/targets"),
+            description: String::from("Updates star bonus repositories (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/config/claims"),
+            description: String::from("Returns per-hotkey claim limits and cooldown settings"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/config/claims"),
+            description: String::from("Updates claim limits (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/config/duplicates"),
+            description: String::from("Returns the near-duplicate fingerprint distance and action"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/config/duplicates"),
+            description: String::from("Updates near-duplicate detection (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/config/quality"),
+            description: String::from("Returns the LLM quality assessment settings"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/config/quality"),
+            description: String::from("Updates LLM quality assessment (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/config/auth"),
+            description: String::from(
+                "Returns the signed request timestamp window and replay cache size",
+            ),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/config/auth"),
+            description: String::from("Updates signed request freshness rules (requires owner)"),
+            requires_auth: true,
+        },
         WasmRouteDefinition {
             method: String::from("GET"),
             path: String::from("/config/timeout"),
@@ -94,37 +246,136 @@ pub fn get_route_definitions() -> Vec<WasmRouteDefinition> {
         WasmRouteDefinition {
             method: String::from("POST"),
             path: String::from("/config/timeout"),
-            description: String::from("Updates timeout configuration (requires auth)"),
+            description: String::from("Updates timeout configuration (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/config/scoring"),
+            description: String::from("Returns current scoring parameters"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/config/scoring"),
+            description: String::from("Updates scoring parameters (requires owner)"),
             requires_auth: true,
         },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/config/decay"),
+            description: String::from("Returns current score decay configuration"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/config/decay"),
+            description: String::from("Updates score decay configuration (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/config/multipliers"),
+            description: String::from("Returns per-repository and per-label point multipliers"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/config/multipliers"),
+            description: String::from("Updates point multipliers (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("GET"),
+            path: String::from("/config/tiers"),
+            description: String::from("Returns label severity tiers used for issue points"),
+            requires_auth: false,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/config/tiers"),
+            description: String::from("Updates label severity tiers (requires owner)"),
+            requires_auth: true,
+        },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/simulate"),
+            description: String::from(
+                "Simulates leaderboard and weights under hypothetical changes (read-only)",
+            ),
+            requires_auth: false,
+        },
         WasmRouteDefinition {
             method: String::from("GET"),
             path: String::from("/get_weights"),
             description: String::from("Returns normalized weight assignments for all miners"),
             requires_auth: false,
         },
+        WasmRouteDefinition {
+            method: String::from("POST"),
+            path: String::from("/get_weights/u16"),
+            description: String::from(
+                "Returns u16 chain weights summing to 65535, optionally keyed by UID",
+            ),
+            requires_auth: false,
+        },
     ]
 }
 
 pub fn handle_route_request(request: &WasmRouteRequest) -> WasmRouteResponse {
-    let path = request.path.as_str();
+    let path = request
+        .path
+        .split_once('?')
+        .map_or(request.path.as_str(), |(p, _)| p);
     let method = request.method.as_str();
 
     match (method, path) {
         ("GET", "/leaderboard") => handlers::handle_leaderboard(request),
+        ("GET", "/leaderboard/history") => handlers::handle_leaderboard_history(request),
+        ("POST", "/leaderboard/rebuild") => handlers::handle_rebuild_leaderboard(request),
         ("GET", "/stats") => handlers::handle_stats(request),
         ("POST", "/register") => handlers::handle_register(request),
         ("POST", "/claim") => handlers::handle_claim(request),
+        ("POST", "/claim/preview") => handlers::handle_claim_preview(request),
         ("GET", "/issues") => handlers::handle_issues(request),
         ("GET", "/issues/pending") => handlers::handle_issues_pending(request),
+        ("GET", "/issues/duplicates") => handlers::handle_issues_duplicates(request),
+        ("GET", "/issues/reviews") => handlers::handle_issues_reviews(request),
+        ("POST", "/issues/assess") => handlers::handle_issues_assess(request),
+        ("POST", "/issues/content/propose") => handlers::handle_issue_content_propose(request),
         ("POST", "/invalid") => handlers::handle_invalid(request),
         ("POST", "/sync/propose") => handlers::handle_sync_propose(request),
         ("GET", "/sync/consensus") => handlers::handle_sync_consensus(request),
         ("POST", "/issue/propose") => handlers::handle_issue_propose(request),
         ("POST", "/issue/consensus") => handlers::handle_issue_consensus(request),
+        ("GET", "/repos") => handlers::handle_get_repo_registry(request),
+        ("POST", "/repos") => handlers::handle_set_repo_registry(request),
+        ("POST", "/stars/propose") => handlers::handle_star_propose(request),
+        ("GET", "/validators") => handlers::handle_get_validators(request),
+        ("POST", "/validators") => handlers::handle_set_validators(request),
+        ("GET", "/stars/targets") => handlers::handle_get_star_targets(request),
+        ("POST", "/stars/targets") => handlers::handle_set_star_targets(request),
+        ("GET", "/config/claims") => handlers::handle_get_claim_limits(request),
+        ("POST", "/config/claims") => handlers::handle_set_claim_limits(request),
+        ("GET", "/config/duplicates") => handlers::handle_get_duplicate_config(request),
+        ("POST", "/config/duplicates") => handlers::handle_set_duplicate_config(request),
+        ("GET", "/config/quality") => handlers::handle_get_quality_config(request),
+        ("POST", "/config/quality") => handlers::handle_set_quality_config(request),
+        ("GET", "/config/auth") => handlers::handle_get_auth_config(request),
+        ("POST", "/config/auth") => handlers::handle_set_auth_config(request),
         ("GET", "/config/timeout") => handlers::handle_get_timeout_config(request),
         ("POST", "/config/timeout") => handlers::handle_set_timeout_config(request),
+        ("GET", "/config/scoring") => handlers::handle_get_scoring_config(request),
+        ("POST", "/config/scoring") => handlers::handle_set_scoring_config(request),
+        ("GET", "/config/decay") => handlers::handle_get_decay_config(request),
+        ("POST", "/config/decay") => handlers::handle_set_decay_config(request),
+        ("GET", "/config/multipliers") => handlers::handle_get_point_multipliers(request),
+        ("POST", "/config/multipliers") => handlers::handle_set_point_multipliers(request),
+        ("GET", "/config/tiers") => handlers::handle_get_label_tiers(request),
+        ("POST", "/config/tiers") => handlers::handle_set_label_tiers(request),
+        ("POST", "/simulate") => handlers::handle_simulate(request),
         ("GET", "/get_weights") => handlers::handle_get_weights(request),
+        ("POST", "/get_weights/u16") => handlers::handle_get_u16_weights(request),
         _ => {
             if method == "GET" {
                 if path.starts_with("/status/") {
@@ -133,6 +384,9 @@ pub fn handle_route_request(request: &WasmRouteRequest) -> WasmRouteResponse {
                 if path.starts_with("/hotkey/") {
                     return handlers::handle_hotkey_details(request);
                 }
+                if path.starts_with("/leaderboard/history/") {
+                    return handlers::handle_hotkey_rank_history(request);
+                }
             }
             WasmRouteResponse {
                 status: 404,
@@ -1,106 +1,1105 @@
+use alloc::collections::{BTreeMap, BTreeSet};
 use alloc::string::String;
 use alloc::vec::Vec;
+use core::cmp::Ordering;
 use serde::{Deserialize, Serialize};
 
+use crate::fixed::{self, Ppb, SCALE};
 use crate::storage;
-use crate::types::LeaderboardEntry;
+use crate::types::{
+    ChainWeight, CreditedIssue, DecayConfig, IssueRecord, LabelTiers, LeaderboardEntry,
+    PointMultipliers, ScoringConfig, SimulationRequest, SimulationResponse, UidMapping,
+    UserBalance,
+};
 
-pub const WEIGHT_PER_POINT: f64 = 0.02;
-pub const STAR_BONUS_PER_REPO: f64 = 0.25;
+pub const WEIGHT_PER_POINT: Ppb = 20_000_000;
+pub const STAR_BONUS_PER_REPO: Ppb = 250_000_000;
+
+pub const MAX_WEIGHT_PER_POINT: Ppb = SCALE;
+pub const MAX_STAR_BONUS_PER_REPO: Ppb = fixed::from_int(10);
+pub const MAX_POINT_MULTIPLIER: Ppb = fixed::from_int(100);
+pub const MAX_TIER_POINTS: Ppb = fixed::from_int(100);
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct WeightAssignment {
+    pub hotkey: String,
+    pub weight: Ppb,
+}
+
+/// Weight in the layout the host reads from `Challenge::get_weights`, with
+/// `weight` as a fraction of 1.0. Scoring stays in fixed point and is only
+/// converted here, so the fraction is derived from identical integers on
+/// every validator.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HostWeightAssignment {
     pub hotkey: String,
     pub weight: f64,
 }
 
-pub fn calculate_weight_from_points(valid_count: u32, star_count: u32) -> f64 {
-    let issue_points = valid_count as f64;
-    let star_points = star_count as f64 * STAR_BONUS_PER_REPO;
-    let total_points = issue_points + star_points;
-    total_points * WEIGHT_PER_POINT
+pub fn to_host_weights(weights: &[WeightAssignment]) -> Vec<HostWeightAssignment> {
+    weights
+        .iter()
+        .map(|w| HostWeightAssignment {
+            hotkey: w.hotkey.clone(),
+            weight: w.weight as f64 / SCALE as f64,
+        })
+        .collect()
 }
 
-pub fn calculate_net_points(valid_count: u32, invalid_count: u32, star_count: u32) -> f64 {
-    let issue_points = valid_count as f64;
-    let star_points = star_count as f64 * STAR_BONUS_PER_REPO;
-    let penalty = if invalid_count > valid_count {
-        (invalid_count - valid_count) as f64
-    } else {
-        0.0
-    };
-    (issue_points + star_points - penalty).max(0.0)
+/// A mapping may name each hotkey and each UID at most once.
+pub fn validate_uid_mapping(uids: &[UidMapping]) -> bool {
+    let mut seen_uids = BTreeSet::new();
+    let mut seen_hotkeys = BTreeSet::new();
+    uids.iter()
+        .all(|m| seen_uids.insert(m.uid) && seen_hotkeys.insert(m.hotkey.as_str()))
+}
+
+pub fn validate_scoring_config(config: &ScoringConfig) -> bool {
+    config.weight_per_point > 0
+        && config.weight_per_point <= MAX_WEIGHT_PER_POINT
+        && config.star_bonus_per_repo <= MAX_STAR_BONUS_PER_REPO
+        && config
+            .max_weight_share
+            .map(|cap| cap > 0 && cap <= SCALE)
+            .unwrap_or(true)
+        && config.burn_fraction <= SCALE
+        && match config.burn_hotkey.as_deref() {
+            Some(hotkey) => !hotkey.is_empty(),
+            None => config.burn_fraction == 0,
+        }
+        && config
+            .ema_alpha
+            .map(|alpha| alpha > 0 && alpha <= SCALE)
+            .unwrap_or(true)
+}
+
+fn valid_multiplier(multiplier: Ppb) -> bool {
+    multiplier > 0 && multiplier <= MAX_POINT_MULTIPLIER
+}
+
+/// Every multiplier must be in `(0, MAX_POINT_MULTIPLIER]`, and each
+/// repository or label may appear only once (compared case-insensitively).
+pub fn validate_point_multipliers(multipliers: &PointMultipliers) -> bool {
+    let mut repos = BTreeSet::new();
+    let repos_valid = multipliers.repos.iter().all(|r| {
+        !r.repo_owner.is_empty()
+            && !r.repo_name.is_empty()
+            && valid_multiplier(r.multiplier)
+            && repos.insert((
+                r.repo_owner.to_ascii_lowercase(),
+                r.repo_name.to_ascii_lowercase(),
+            ))
+    });
+    let mut labels = BTreeSet::new();
+    repos_valid
+        && multipliers.labels.iter().all(|l| {
+            !l.label.is_empty()
+                && valid_multiplier(l.multiplier)
+                && labels.insert(l.label.to_ascii_lowercase())
+        })
+}
+
+fn valid_tier_points(points: Ppb) -> bool {
+    points > 0 && points <= MAX_TIER_POINTS
+}
+
+/// `base_points` and every tier must be worth `(0, MAX_TIER_POINTS]`. Tiers
+/// need a non-empty label, are listed from most to fewest points, and may
+/// not repeat a label (compared case-insensitively).
+pub fn validate_label_tiers(tiers: &LabelTiers) -> bool {
+    let mut labels = BTreeSet::new();
+    valid_tier_points(tiers.base_points)
+        && tiers.tiers.iter().all(|t| {
+            !t.label.is_empty()
+                && valid_tier_points(t.points)
+                && labels.insert(t.label.to_ascii_lowercase())
+        })
+        && tiers.tiers.windows(2).all(|w| w[0].points > w[1].points)
+}
+
+pub fn calculate_weight_from_points(
+    issue_points: Ppb,
+    star_count: u32,
+    config: &ScoringConfig,
+) -> Ppb {
+    let star_points = fixed::mul_int(config.star_bonus_per_repo, star_count as u64);
+    let total_points = issue_points.saturating_add(star_points);
+    fixed::mul(total_points, config.weight_per_point)
+}
+
+/// Fraction of an issue's value remaining after `age_epochs`. Halves every
+/// `half_life_epochs` and interpolates linearly between halvings so the
+/// result stays exact in integer arithmetic.
+pub fn decay_factor(age_epochs: u64, half_life_epochs: u64) -> Ppb {
+    if half_life_epochs == 0 {
+        return SCALE;
+    }
+    let halvings = age_epochs / half_life_epochs;
+    if halvings >= 64 {
+        return 0;
+    }
+    let base = SCALE >> halvings;
+    let remainder = age_epochs % half_life_epochs;
+    base - (base as u128 * remainder as u128 / (2 * half_life_epochs as u128)) as u64
 }
 
-pub fn calculate_weights_from_leaderboard(entries: &[LeaderboardEntry]) -> Vec<WeightAssignment> {
-    let mut weights: Vec<WeightAssignment> = entries
+fn has_label(issue: &IssueRecord, label: &str) -> bool {
+    issue.labels.iter().any(|l| l.eq_ignore_ascii_case(label))
+}
+
+pub fn calculate_tier_points(issue: &IssueRecord, tiers: &LabelTiers) -> Ppb {
+    tiers
+        .tiers
+        .iter()
+        .filter(|t| has_label(issue, &t.label))
+        .map(|t| t.points)
+        .max()
+        .unwrap_or(tiers.base_points)
+}
+
+/// Points credited for a valid issue: its severity tier points, scaled by
+/// the repository multiplier and the highest matching component multiplier.
+pub fn calculate_issue_value(
+    issue: &IssueRecord,
+    multipliers: &PointMultipliers,
+    tiers: &LabelTiers,
+) -> Ppb {
+    let repo_multiplier = multipliers
+        .repos
         .iter()
-        .filter(|e| !e.is_penalized && e.score > 0.0)
-        .map(|e| WeightAssignment {
-            hotkey: e.hotkey.clone(),
-            weight: e.score,
+        .find(|r| {
+            r.repo_owner.eq_ignore_ascii_case(&issue.repo_owner)
+                && r.repo_name.eq_ignore_ascii_case(&issue.repo_name)
         })
-        .collect();
+        .map(|r| r.multiplier)
+        .unwrap_or(SCALE);
+
+    let label_multiplier = multipliers
+        .labels
+        .iter()
+        .filter(|m| has_label(issue, &m.label))
+        .map(|m| m.multiplier)
+        .max()
+        .unwrap_or(SCALE);
+
+    fixed::mul(
+        fixed::mul(calculate_tier_points(issue, tiers), repo_multiplier),
+        label_multiplier,
+    )
+}
+
+/// Returns `(lifetime_points, decayed_points)` for a miner. Valid issues
+/// credited before per-issue tracking existed count as one point each.
+pub fn calculate_issue_points(
+    credited: &[CreditedIssue],
+    valid_count: u32,
+    current_epoch: u64,
+    config: &DecayConfig,
+) -> (Ppb, Ppb) {
+    let untracked = fixed::from_int((valid_count as u64).saturating_sub(credited.len() as u64));
+    let lifetime = credited
+        .iter()
+        .fold(0u64, |acc, i| acc.saturating_add(i.points));
+    let decayed = credited.iter().fold(0u64, |acc, i| {
+        let factor = decay_factor(
+            current_epoch.saturating_sub(i.recorded_epoch),
+            config.half_life_epochs,
+        );
+        acc.saturating_add(fixed::mul(i.points, factor))
+    });
+    (
+        lifetime.saturating_add(untracked),
+        decayed.saturating_add(untracked),
+    )
+}
 
-    let total: f64 = weights.iter().map(|w| w.weight).sum();
-    if total > 0.0 {
-        for w in &mut weights {
-            w.weight /= total;
+pub fn hotkey_issue_points(hotkey: &str, balance: &UserBalance) -> (Ppb, Ppb) {
+    let credited = storage::get_credited_issues(hotkey);
+    calculate_issue_points(
+        &credited,
+        balance.valid_count,
+        current_epoch(),
+        &storage::get_decay_config(),
+    )
+}
+
+pub fn calculate_net_points(
+    issue_points: Ppb,
+    valid_count: u32,
+    invalid_count: u32,
+    star_count: u32,
+    config: &ScoringConfig,
+) -> Ppb {
+    let star_points = fixed::mul_int(config.star_bonus_per_repo, star_count as u64);
+    let penalty = fixed::from_int(invalid_count.saturating_sub(valid_count) as u64);
+    issue_points
+        .saturating_add(star_points)
+        .saturating_sub(penalty)
+}
+
+/// Splits `total` across `raw` scores so that no share exceeds `cap`. Shares
+/// above the cap are clamped and the excess goes to the uncapped miners in
+/// proportion to their scores, repeating until no share exceeds the cap.
+/// If the cap cannot be met (fewer than `1 / cap` miners) everyone gets an
+/// equal share.
+fn capped_shares(raw: &[(&str, u64)], cap: Ppb, total: Ppb) -> Vec<Ppb> {
+    if (cap as u128) * (raw.len() as u128) <= total as u128 {
+        let equal: Vec<(&str, u64)> = raw.iter().map(|(k, _)| (*k, 1)).collect();
+        return fixed::apportion(&equal, total);
+    }
+
+    let mut capped = alloc::vec![false; raw.len()];
+    loop {
+        let capped_count = capped.iter().filter(|c| **c).count() as u64;
+        let remaining = total - cap * capped_count;
+        let uncapped: Vec<(&str, u64)> = raw
+            .iter()
+            .zip(&capped)
+            .filter(|(_, c)| !**c)
+            .map(|(r, _)| *r)
+            .collect();
+        let uncapped_shares = fixed::apportion(&uncapped, remaining);
+
+        let mut shares = Vec::with_capacity(raw.len());
+        let mut next = uncapped_shares.iter();
+        let mut newly_capped = false;
+        for c in capped.iter_mut() {
+            if *c {
+                shares.push(cap);
+                continue;
+            }
+            let share = next.next().copied().unwrap_or(0);
+            if share > cap {
+                *c = true;
+                newly_capped = true;
+            }
+            shares.push(share);
+        }
+        if !newly_capped {
+            return shares;
         }
     }
+}
 
-    weights.sort_by(|a, b| {
-        b.weight
-            .partial_cmp(&a.weight)
-            .unwrap_or(core::cmp::Ordering::Equal)
-    });
+pub fn calculate_weights_from_leaderboard(
+    entries: &[LeaderboardEntry],
+    config: &ScoringConfig,
+) -> Vec<WeightAssignment> {
+    let mut eligible: Vec<&LeaderboardEntry> = entries
+        .iter()
+        .filter(|e| !e.is_penalized && e.score > 0)
+        .collect();
+    eligible.sort_by(|a, b| compare_entries(a, b));
+    let raw: Vec<(&str, u64)> = eligible
+        .iter()
+        .map(|e| (e.hotkey.as_str(), e.score))
+        .collect();
+
+    let burn_weight = match config.burn_hotkey {
+        Some(_) if raw.is_empty() => SCALE,
+        Some(_) => config.burn_fraction,
+        None => 0,
+    };
+    let miner_total = SCALE - burn_weight;
+
+    let shares = match config.max_weight_share {
+        Some(cap) => capped_shares(&raw, cap, miner_total),
+        None => fixed::apportion(&raw, miner_total),
+    };
+
+    let mut weights: Vec<WeightAssignment> = raw
+        .iter()
+        .zip(shares)
+        .filter(|(_, weight)| *weight > 0)
+        .map(|((hotkey, _), weight)| WeightAssignment {
+            hotkey: String::from(*hotkey),
+            weight,
+        })
+        .collect();
+
+    // A burn hotkey that is also a miner gets one entry holding both
+    // allocations, so the u16 export never repeats a UID.
+    if let Some(ref burn_hotkey) = config.burn_hotkey {
+        if burn_weight > 0 {
+            match weights.iter_mut().find(|w| &w.hotkey == burn_hotkey) {
+                Some(existing) => existing.weight += burn_weight,
+                None => weights.push(WeightAssignment {
+                    hotkey: burn_hotkey.clone(),
+                    weight: burn_weight,
+                }),
+            }
+        }
+    }
 
     weights
 }
 
+/// Converts normalized weights into u16 chain weights summing to exactly
+/// `u16::MAX` by largest remainder. When `uids` is non-empty, hotkeys without
+/// a UID are dropped before apportioning. If no remaining hotkey has weight
+/// there is nothing to apportion and the result is empty.
+pub fn calculate_u16_weights(
+    weights: &[WeightAssignment],
+    uids: &[UidMapping],
+) -> Vec<ChainWeight> {
+    let mapped: Vec<(&WeightAssignment, Option<u16>)> = weights
+        .iter()
+        .map(|w| (w, uids.iter().find(|m| m.hotkey == w.hotkey).map(|m| m.uid)))
+        .filter(|(_, uid)| uids.is_empty() || uid.is_some())
+        .collect();
+    if mapped.iter().all(|(w, _)| w.weight == 0) {
+        return Vec::new();
+    }
+
+    let shares: Vec<(&str, u64)> = mapped
+        .iter()
+        .map(|(w, _)| (w.hotkey.as_str(), w.weight))
+        .collect();
+    let apportioned = fixed::apportion(&shares, u16::MAX as u64);
+
+    mapped
+        .iter()
+        .zip(apportioned)
+        .map(|((w, uid), weight)| ChainWeight {
+            hotkey: w.hotkey.clone(),
+            uid: *uid,
+            weight: weight as u16,
+        })
+        .collect()
+}
+
+/// Inputs needed to score a single miner, loaded from storage or built by
+/// the simulator.
+pub struct MinerState {
+    pub hotkey: String,
+    pub github_username: String,
+    pub registered_epoch: u64,
+    pub balance: UserBalance,
+    pub credited: Vec<CreditedIssue>,
+    pub pending_issues: u32,
+}
+
+pub fn current_epoch() -> u64 {
+    let epoch = platform_challenge_sdk_wasm::host_functions::host_consensus_get_epoch();
+    if epoch >= 0 {
+        epoch as u64
+    } else {
+        0
+    }
+}
+
+/// Open issues that maintainers have not yet labeled valid or invalid.
+fn is_pending(issue: &IssueRecord) -> bool {
+    !issue.is_closed && !issue.has_valid_label && !issue.has_invalid_label
+}
+
+/// Pending issues authored by `github_username`.
+pub fn count_pending_issues(synced: &[IssueRecord], github_username: &str) -> u32 {
+    synced
+        .iter()
+        .filter(|i| is_pending(i) && i.author.eq_ignore_ascii_case(github_username))
+        .count() as u32
+}
+
+/// Pending issue counts keyed by lowercase author, built in one pass so a
+/// rebuild does not rescan the synced issues for every miner.
+pub fn pending_issues_by_author(synced: &[IssueRecord]) -> BTreeMap<String, u32> {
+    let mut counts = BTreeMap::new();
+    for issue in synced.iter().filter(|i| is_pending(i)) {
+        *counts.entry(issue.author.to_ascii_lowercase()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Loads `hotkey`'s stored state; `pending_issues` maps the registered
+/// GitHub username to its pending issue count.
+fn load_miner_state(hotkey: &str, pending_issues: impl FnOnce(&str) -> u32) -> Option<MinerState> {
+    let registration = storage::get_user_by_hotkey(hotkey)?;
+    Some(MinerState {
+        hotkey: String::from(hotkey),
+        pending_issues: pending_issues(&registration.github_username),
+        github_username: registration.github_username,
+        registered_epoch: registration.registered_epoch,
+        balance: storage::get_user_balance(hotkey),
+        credited: storage::get_credited_issues(hotkey),
+    })
+}
+
+pub fn load_miner_states() -> Vec<MinerState> {
+    let pending = pending_issues_by_author(&storage::get_synced_issues());
+    storage::get_registered_hotkeys()
+        .iter()
+        .filter_map(|hotkey| {
+            load_miner_state(hotkey, |username| {
+                pending
+                    .get(&username.to_ascii_lowercase())
+                    .copied()
+                    .unwrap_or(0)
+            })
+        })
+        .collect()
+}
+
+fn build_entry(
+    miner: &MinerState,
+    current_epoch: u64,
+    decay_config: &DecayConfig,
+    config: &ScoringConfig,
+) -> LeaderboardEntry {
+    let balance = &miner.balance;
+    let (lifetime_points, decayed_points) = calculate_issue_points(
+        &miner.credited,
+        balance.valid_count,
+        current_epoch,
+        decay_config,
+    );
+
+    let net_points = calculate_net_points(
+        decayed_points,
+        balance.valid_count,
+        balance.invalid_count,
+        balance.star_count,
+        config,
+    );
+    let score = calculate_weight_from_points(decayed_points, balance.star_count, config);
+
+    LeaderboardEntry {
+        rank: 0,
+        hotkey: miner.hotkey.clone(),
+        github_username: miner.github_username.clone(),
+        score,
+        lifetime_points,
+        decayed_points,
+        valid_issues: balance.valid_count,
+        invalid_issues: balance.invalid_count,
+        pending_issues: miner.pending_issues,
+        star_count: balance.star_count,
+        star_bonus: fixed::mul_int(config.star_bonus_per_repo, balance.star_count as u64),
+        net_points,
+        is_penalized: balance.is_penalized,
+        achieved_epoch: miner
+            .credited
+            .iter()
+            .map(|i| i.recorded_epoch)
+            .max()
+            .unwrap_or(miner.registered_epoch),
+        last_epoch: current_epoch,
+    }
+}
+
+/// Total leaderboard order: higher score, then higher net points, then the
+/// earlier epoch at which the miner reached its standing, then hotkey.
+pub fn compare_entries(a: &LeaderboardEntry, b: &LeaderboardEntry) -> Ordering {
+    b.score
+        .cmp(&a.score)
+        .then_with(|| b.net_points.cmp(&a.net_points))
+        .then_with(|| a.achieved_epoch.cmp(&b.achieved_epoch))
+        .then_with(|| a.hotkey.cmp(&b.hotkey))
+}
+
+fn shares_rank(a: &LeaderboardEntry, b: &LeaderboardEntry) -> bool {
+    a.score == b.score && a.net_points == b.net_points && a.achieved_epoch == b.achieved_epoch
+}
+
+/// Assigns competition ranks (1, 1, 3, ...) to entries sorted by
+/// `compare_entries`. Entries that differ only by hotkey share a rank.
+fn assign_ranks(entries: &mut [LeaderboardEntry]) {
+    for i in 0..entries.len() {
+        entries[i].rank = if i > 0 && shares_rank(&entries[i - 1], &entries[i]) {
+            entries[i - 1].rank
+        } else {
+            (i + 1) as u32
+        };
+    }
+}
+
+pub fn build_leaderboard(
+    miners: &[MinerState],
+    current_epoch: u64,
+    decay_config: &DecayConfig,
+    config: &ScoringConfig,
+) -> Vec<LeaderboardEntry> {
+    let mut entries: Vec<LeaderboardEntry> = miners
+        .iter()
+        .map(|m| build_entry(m, current_epoch, decay_config, config))
+        .collect();
+
+    entries.sort_by(compare_entries);
+    assign_ranks(&mut entries);
+
+    entries
+}
+
 pub fn rebuild_leaderboard() {
-    let hotkeys = storage::get_registered_hotkeys();
-    let mut entries = Vec::with_capacity(hotkeys.len());
+    let epoch = current_epoch();
+    storage::observe_epoch(epoch, crate::signature::host_now_secs());
+    let config = storage::get_scoring_config();
 
-    for hotkey in &hotkeys {
-        let balance = storage::get_user_balance(hotkey);
-        let github_username = storage::get_github_by_hotkey(hotkey).unwrap_or_default();
+    let previous = storage::get_leaderboard();
+    if previous
+        .first()
+        .map(|e| e.last_epoch < epoch)
+        .unwrap_or(false)
+    {
+        advance_weight_ema(&previous, &config);
+    }
+
+    let miners = load_miner_states();
+    let entries = build_leaderboard(&miners, epoch, &storage::get_decay_config(), &config);
+    storage::store_leaderboard(&entries);
+}
+
+/// Blends `raw` into `previous` as `alpha * raw + (1 - alpha) * previous`
+/// per hotkey, then re-apportions so the result sums to exactly `SCALE`.
+fn blend_weights(
+    previous: &[WeightAssignment],
+    raw: &[WeightAssignment],
+    alpha: Ppb,
+) -> Vec<WeightAssignment> {
+    if previous.is_empty() {
+        return raw.to_vec();
+    }
+
+    let mut hotkeys: Vec<&str> = previous
+        .iter()
+        .chain(raw)
+        .map(|w| w.hotkey.as_str())
+        .collect();
+    hotkeys.sort_unstable();
+    hotkeys.dedup();
+
+    let weight_of = |weights: &[WeightAssignment], hotkey: &str| {
+        weights
+            .iter()
+            .find(|w| w.hotkey == hotkey)
+            .map_or(0, |w| w.weight)
+    };
+    let blended: Vec<(&str, u64)> = hotkeys
+        .into_iter()
+        .map(|h| {
+            let smoothed = fixed::mul(weight_of(raw, h), alpha)
+                .saturating_add(fixed::mul(weight_of(previous, h), SCALE - alpha));
+            (h, smoothed)
+        })
+        .filter(|(_, w)| *w > 0)
+        .collect();
+
+    let shares = fixed::apportion(&blended, SCALE);
+    blended
+        .iter()
+        .zip(shares)
+        .map(|((hotkey, _), weight)| WeightAssignment {
+            hotkey: String::from(*hotkey),
+            weight,
+        })
+        .collect()
+}
+
+/// Folds the final weights of a finished epoch into the stored moving
+/// average. Runs at most once per epoch, from the retired leaderboard, so
+/// every validator derives the same smoothed state.
+fn advance_weight_ema(retired: &[LeaderboardEntry], config: &ScoringConfig) {
+    let alpha = match config.ema_alpha {
+        Some(a) => a,
+        None => return,
+    };
+    let epoch = match retired.first() {
+        Some(e) => e.last_epoch,
+        None => return,
+    };
+
+    let mut state = storage::get_weight_ema();
+    if state.epoch >= epoch && !state.weights.is_empty() {
+        return;
+    }
+    let raw = calculate_weights_from_leaderboard(retired, config);
+    state.weights = blend_weights(&state.weights, &raw, alpha);
+    state.epoch = epoch;
+    storage::store_weight_ema(&state);
+}
 
-        let net_points = calculate_net_points(
-            balance.valid_count,
-            balance.invalid_count,
-            balance.star_count,
+/// Weights to submit on-chain: the smoothed weights when EMA is enabled and
+/// initialized, otherwise the weights of the live leaderboard. Nothing is
+/// written; the average only advances on write paths through
+/// `rebuild_leaderboard`. A leaderboard left over from an earlier epoch is
+/// rescored in memory so decay still applies.
+pub fn current_weights(config: &ScoringConfig) -> Vec<WeightAssignment> {
+    if config.ema_alpha.is_some() {
+        let state = storage::get_weight_ema();
+        if !state.weights.is_empty() {
+            return state.weights;
+        }
+    }
+
+    let epoch = current_epoch();
+    let entries = storage::get_leaderboard();
+    let stale = entries
+        .first()
+        .map(|e| e.last_epoch < epoch)
+        .unwrap_or(false);
+    if stale {
+        let live = build_leaderboard(
+            &load_miner_states(),
+            epoch,
+            &storage::get_decay_config(),
+            config,
         );
-        let score = calculate_weight_from_points(balance.valid_count, balance.star_count);
+        return calculate_weights_from_leaderboard(&live, config);
+    }
+    calculate_weights_from_leaderboard(&entries, config)
+}
+
+/// Recomputes a single miner's entry and moves it to its new rank instead
+/// of rescoring every registered hotkey. Decay makes every score depend on
+/// the epoch, so the first update in a new epoch (or a missing leaderboard)
+/// falls back to a full rebuild to reconcile.
+pub fn update_leaderboard_entry(hotkey: &str) {
+    let epoch = current_epoch();
+    let mut entries = storage::get_leaderboard();
+    if entries.is_empty() || entries.iter().any(|e| e.last_epoch != epoch) {
+        rebuild_leaderboard();
+        return;
+    }
 
-        let epoch = platform_challenge_sdk_wasm::host_functions::host_consensus_get_epoch();
-        let current_epoch = if epoch >= 0 { epoch as u64 } else { 0 };
+    let synced = storage::get_synced_issues();
+    let miner = match load_miner_state(hotkey, |username| count_pending_issues(&synced, username)) {
+        Some(m) => m,
+        None => return,
+    };
+    let entry = build_entry(
+        &miner,
+        epoch,
+        &storage::get_decay_config(),
+        &storage::get_scoring_config(),
+    );
+
+    if let Some(i) = entries.iter().position(|e| e.hotkey == hotkey) {
+        entries.remove(i);
+    }
+    let new_index = entries.partition_point(|e| compare_entries(e, &entry) == Ordering::Less);
+    entries.insert(new_index, entry);
+    assign_ranks(&mut entries);
+
+    storage::store_leaderboard(&entries);
+}
+
+/// Projects `hotkey`'s balance, score and weight share if `credited` were
+/// added to its credited issues now. Every miner is rescored with the same
+/// code as `rebuild_leaderboard`, and nothing is stored. The weight is the
+/// share before any EMA smoothing.
+pub fn project_claim(hotkey: &str, credited: &[CreditedIssue]) -> (UserBalance, Ppb, Ppb) {
+    let epoch = current_epoch();
+    let config = storage::get_scoring_config();
+
+    let mut miners = load_miner_states();
+    let index = match miners.iter().position(|m| m.hotkey == hotkey) {
+        Some(i) => i,
+        None => {
+            miners.push(MinerState {
+                hotkey: String::from(hotkey),
+                github_username: String::new(),
+                registered_epoch: epoch,
+                balance: storage::get_user_balance(hotkey),
+                credited: storage::get_credited_issues(hotkey),
+                pending_issues: 0,
+            });
+            miners.len() - 1
+        }
+    };
+    let miner = &mut miners[index];
+    miner.credited.extend_from_slice(credited);
+    let balance = &mut miner.balance;
+    balance.valid_count = balance.valid_count.saturating_add(credited.len() as u32);
+    balance.valid_points = credited
+        .iter()
+        .fold(balance.valid_points, |acc, i| acc.saturating_add(i.points));
+    balance.is_penalized = storage::is_balance_penalized(balance);
+    let balance = balance.clone();
+
+    let leaderboard = build_leaderboard(&miners, epoch, &storage::get_decay_config(), &config);
+    let score = leaderboard
+        .iter()
+        .find(|e| e.hotkey == hotkey)
+        .map(|e| e.score)
+        .unwrap_or(0);
+    let weight = calculate_weights_from_leaderboard(&leaderboard, &config)
+        .iter()
+        .find(|w| w.hotkey == hotkey)
+        .map(|w| w.weight)
+        .unwrap_or(0);
+
+    (balance, score, weight)
+}
+
+/// Applies hypothetical changes to a copy of the current miner state and
+/// scores it with the same code as `rebuild_leaderboard`. Nothing is stored.
+pub fn simulate(request: &SimulationRequest) -> SimulationResponse {
+    let epoch = current_epoch();
+    let scoring_config = request
+        .scoring
+        .clone()
+        .unwrap_or_else(storage::get_scoring_config);
+    let decay_config = request
+        .decay
+        .clone()
+        .unwrap_or_else(storage::get_decay_config);
+    let base_points = storage::get_label_tiers().base_points;
+
+    let mut miners = load_miner_states();
+    for adjustment in &request.adjustments {
+        let index = match miners.iter().position(|m| m.hotkey == adjustment.hotkey) {
+            Some(i) => i,
+            None => {
+                miners.push(MinerState {
+                    hotkey: adjustment.hotkey.clone(),
+                    github_username: String::new(),
+                    registered_epoch: epoch,
+                    balance: UserBalance::default(),
+                    credited: Vec::new(),
+                    pending_issues: 0,
+                });
+                miners.len() - 1
+            }
+        };
+        let miner = &mut miners[index];
+        let points = adjustment.points_per_issue.unwrap_or(base_points);
+
+        for _ in 0..adjustment.additional_valid_issues {
+            miner.credited.push(CreditedIssue {
+                issue_number: 0,
+                repo_owner: String::new(),
+                repo_name: String::new(),
+                recorded_epoch: epoch,
+                points,
+            });
+        }
+        let balance = &mut miner.balance;
+        balance.valid_count = balance
+            .valid_count
+            .saturating_add(adjustment.additional_valid_issues);
+        balance.valid_points = balance.valid_points.saturating_add(fixed::mul_int(
+            points,
+            adjustment.additional_valid_issues as u64,
+        ));
+        balance.invalid_count = balance
+            .invalid_count
+            .saturating_add(adjustment.additional_invalid_issues);
+        if let Some(star_count) = adjustment.star_count {
+            balance.star_count = star_count;
+        }
+        balance.is_penalized = storage::is_balance_penalized(balance);
+    }
+
+    let leaderboard = build_leaderboard(&miners, epoch, &decay_config, &scoring_config);
+    let weights = calculate_weights_from_leaderboard(&leaderboard, &scoring_config);
 
-        entries.push(LeaderboardEntry {
+    SimulationResponse {
+        leaderboard,
+        weights,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{LabelMultiplier, LabelTier, RepoMultiplier};
+
+    #[test]
+    fn decay_halves_at_each_half_life() {
+        assert_eq!(decay_factor(0, 10), SCALE);
+        assert_eq!(decay_factor(10, 10), SCALE / 2);
+        assert_eq!(decay_factor(20, 10), SCALE / 4);
+        assert_eq!(decay_factor(5, 10), 750_000_000);
+    }
+
+    #[test]
+    fn decay_is_monotone() {
+        for half_life in [1, 7, 30] {
+            for age in 0..200 {
+                assert!(decay_factor(age + 1, half_life) <= decay_factor(age, half_life));
+            }
+        }
+    }
+
+    #[test]
+    fn capped_shares_respect_cap() {
+        let shares = capped_shares(&[("a", 90), ("b", 5), ("c", 5)], SCALE / 2, SCALE);
+        assert_eq!(shares, [500_000_000, 250_000_000, 250_000_000]);
+    }
+
+    #[test]
+    fn capped_shares_redistribute_until_settled() {
+        let cap = 400_000_000;
+        let shares = capped_shares(&[("a", 80), ("b", 15), ("c", 5)], cap, SCALE);
+        assert_eq!(shares, [cap, cap, 200_000_000]);
+        assert_eq!(shares.iter().sum::<u64>(), SCALE);
+    }
+
+    #[test]
+    fn capped_shares_split_equally_when_cap_unreachable() {
+        let shares = capped_shares(&[("a", 99), ("b", 1)], 400_000_000, SCALE);
+        assert_eq!(shares, [500_000_000, 500_000_000]);
+    }
+
+    #[test]
+    fn decay_bounds() {
+        assert_eq!(decay_factor(1_000, 0), SCALE);
+        assert_eq!(decay_factor(64 * 10, 10), 0);
+        assert_eq!(decay_factor(u64::MAX, 1), 0);
+    }
+
+    fn entry(hotkey: &str, score: Ppb) -> LeaderboardEntry {
+        LeaderboardEntry {
             rank: 0,
-            hotkey: hotkey.clone(),
-            github_username,
+            hotkey: String::from(hotkey),
+            github_username: String::from(hotkey),
             score,
-            valid_issues: balance.valid_count,
-            invalid_issues: balance.invalid_count,
+            lifetime_points: score,
+            decayed_points: score,
+            valid_issues: 1,
+            invalid_issues: 0,
             pending_issues: 0,
-            star_count: balance.star_count,
-            star_bonus: balance.star_count as f64 * STAR_BONUS_PER_REPO,
-            net_points,
-            is_penalized: balance.is_penalized,
-            last_epoch: current_epoch,
+            star_count: 0,
+            star_bonus: 0,
+            net_points: score,
+            is_penalized: false,
+            achieved_epoch: 0,
+            last_epoch: 0,
+        }
+    }
+
+    fn burn_config(hotkey: &str, fraction: Ppb) -> ScoringConfig {
+        ScoringConfig {
+            burn_hotkey: Some(String::from(hotkey)),
+            burn_fraction: fraction,
+            ..ScoringConfig::default()
+        }
+    }
+
+    fn weight_pairs(weights: &[WeightAssignment]) -> Vec<(&str, Ppb)> {
+        weights
+            .iter()
+            .map(|w| (w.hotkey.as_str(), w.weight))
+            .collect()
+    }
+
+    #[test]
+    fn burn_hotkey_on_leaderboard_gets_one_merged_weight() {
+        let entries = [entry("a", 30), entry("burn", 10)];
+        let weights = calculate_weights_from_leaderboard(&entries, &burn_config("burn", SCALE / 2));
+        assert_eq!(
+            weight_pairs(&weights),
+            [("a", 375_000_000), ("burn", 625_000_000)]
+        );
+    }
+
+    #[test]
+    fn empty_leaderboard_burns_everything() {
+        let weights = calculate_weights_from_leaderboard(&[], &burn_config("burn", SCALE / 10));
+        assert_eq!(weight_pairs(&weights), [("burn", SCALE)]);
+
+        let penalized = LeaderboardEntry {
+            is_penalized: true,
+            ..entry("a", 10)
+        };
+        let weights = calculate_weights_from_leaderboard(&[penalized], &burn_config("burn", 0));
+        assert_eq!(weight_pairs(&weights), [("burn", SCALE)]);
+    }
+
+    #[test]
+    fn burn_fraction_leaves_the_rest_to_miners() {
+        let entries = [entry("a", 30), entry("b", 10)];
+        let weights = calculate_weights_from_leaderboard(&entries, &burn_config("burn", SCALE / 5));
+        assert_eq!(
+            weight_pairs(&weights),
+            [
+                ("a", 600_000_000),
+                ("b", 200_000_000),
+                ("burn", 200_000_000)
+            ]
+        );
+        assert!(calculate_weights_from_leaderboard(&[], &ScoringConfig::default()).is_empty());
+    }
+
+    fn assignment(hotkey: &str, weight: Ppb) -> WeightAssignment {
+        WeightAssignment {
+            hotkey: String::from(hotkey),
+            weight,
+        }
+    }
+
+    fn uid(hotkey: &str, uid: u16) -> UidMapping {
+        UidMapping {
+            hotkey: String::from(hotkey),
+            uid,
+        }
+    }
+
+    #[test]
+    fn u16_weights_sum_to_max() {
+        let weights = [
+            assignment("a", 333_333_333),
+            assignment("b", 333_333_333),
+            assignment("c", 333_333_334),
+        ];
+        let chain = calculate_u16_weights(&weights, &[]);
+        assert_eq!(chain.len(), 3);
+        assert!(chain.iter().all(|w| w.uid.is_none()));
+        assert_eq!(
+            chain.iter().map(|w| w.weight as u64).sum::<u64>(),
+            u16::MAX as u64
+        );
+    }
+
+    #[test]
+    fn u16_weights_drop_unmapped_hotkeys() {
+        let weights = [
+            assignment("a", SCALE / 4),
+            assignment("b", SCALE / 4),
+            assignment("c", SCALE / 2),
+        ];
+        let chain = calculate_u16_weights(&weights, &[uid("a", 3), uid("c", 7)]);
+        let exported: Vec<(Option<u16>, u16)> = chain.iter().map(|w| (w.uid, w.weight)).collect();
+        assert_eq!(exported, [(Some(3), 21_845), (Some(7), 43_690)]);
+
+        assert!(calculate_u16_weights(&weights, &[uid("z", 1)]).is_empty());
+        assert!(calculate_u16_weights(&[assignment("a", 0)], &[]).is_empty());
+    }
+
+    #[test]
+    fn ties_share_competition_rank() {
+        let mut entries = alloc::vec![entry("c", 5), entry("b", 10), entry("a", 10)];
+        entries.sort_by(compare_entries);
+        assign_ranks(&mut entries);
+        let ranked: Vec<(&str, u32)> = entries
+            .iter()
+            .map(|e| (e.hotkey.as_str(), e.rank))
+            .collect();
+        assert_eq!(ranked, [("a", 1), ("b", 1), ("c", 3)]);
+    }
+
+    #[test]
+    fn earlier_achievement_breaks_score_ties() {
+        let late = LeaderboardEntry {
+            achieved_epoch: 9,
+            ..entry("a", 10)
+        };
+        let early = LeaderboardEntry {
+            achieved_epoch: 4,
+            ..entry("b", 10)
+        };
+        let mut entries = alloc::vec![late, early];
+        entries.sort_by(compare_entries);
+        assign_ranks(&mut entries);
+        let ranked: Vec<(&str, u32)> = entries
+            .iter()
+            .map(|e| (e.hotkey.as_str(), e.rank))
+            .collect();
+        assert_eq!(ranked, [("b", 1), ("a", 2)]);
+    }
+
+    fn multipliers(repo: Ppb, label: Ppb) -> PointMultipliers {
+        PointMultipliers {
+            repos: alloc::vec![RepoMultiplier {
+                repo_owner: String::from("CortexLM"),
+                repo_name: String::from("cortex"),
+                multiplier: repo,
+            }],
+            labels: alloc::vec![LabelMultiplier {
+                label: String::from("cortex"),
+                multiplier: label,
+            }],
+        }
+    }
+
+    #[test]
+    fn point_multipliers_bounds() {
+        assert!(validate_point_multipliers(&PointMultipliers::default()));
+        assert!(validate_point_multipliers(&multipliers(
+            SCALE / 2,
+            MAX_POINT_MULTIPLIER
+        )));
+        assert!(!validate_point_multipliers(&multipliers(0, SCALE)));
+        assert!(!validate_point_multipliers(&multipliers(SCALE, 0)));
+        assert!(!validate_point_multipliers(&multipliers(
+            SCALE,
+            MAX_POINT_MULTIPLIER + 1
+        )));
+        assert!(!validate_point_multipliers(&multipliers(u64::MAX, SCALE)));
+    }
+
+    #[test]
+    fn point_multipliers_reject_duplicates() {
+        let mut table = multipliers(SCALE, SCALE);
+        table.labels.push(LabelMultiplier {
+            label: String::from("Cortex"),
+            multiplier: SCALE,
         });
+        assert!(!validate_point_multipliers(&table));
+
+        let mut table = multipliers(SCALE, SCALE);
+        table.repos.push(RepoMultiplier {
+            repo_owner: String::from("cortexlm"),
+            repo_name: String::from("CORTEX"),
+            multiplier: SCALE,
+        });
+        assert!(!validate_point_multipliers(&table));
     }
 
-    entries.sort_by(|a, b| {
-        b.score
-            .partial_cmp(&a.score)
-            .unwrap_or(core::cmp::Ordering::Equal)
-    });
+    fn tiers(points: &[Ppb]) -> LabelTiers {
+        LabelTiers {
+            base_points: SCALE,
+            tiers: points
+                .iter()
+                .enumerate()
+                .map(|(i, &points)| LabelTier {
+                    label: alloc::format!("tier-{i}"),
+                    points,
+                })
+                .collect(),
+        }
+    }
 
-    for (i, entry) in entries.iter_mut().enumerate() {
-        entry.rank = (i + 1) as u32;
+    #[test]
+    fn label_tiers_must_be_ordered() {
+        assert!(validate_label_tiers(&LabelTiers::default()));
+        assert!(validate_label_tiers(&tiers(&[
+            5 * SCALE,
+            2 * SCALE,
+            SCALE / 2
+        ])));
+        assert!(!validate_label_tiers(&tiers(&[SCALE / 2, 5 * SCALE])));
+        assert!(!validate_label_tiers(&tiers(&[2 * SCALE, 2 * SCALE])));
     }
 
-    storage::store_leaderboard(&entries);
+    #[test]
+    fn label_tiers_bounds() {
+        assert!(validate_label_tiers(&tiers(&[MAX_TIER_POINTS])));
+        assert!(!validate_label_tiers(&tiers(&[MAX_TIER_POINTS + 1])));
+        assert!(!validate_label_tiers(&tiers(&[SCALE, 0])));
+        assert!(!validate_label_tiers(&LabelTiers {
+            base_points: 0,
+            ..LabelTiers::default()
+        }));
+
+        let mut unlabeled = tiers(&[2 * SCALE]);
+        unlabeled.tiers[0].label.clear();
+        assert!(!validate_label_tiers(&unlabeled));
+
+        let mut repeated = tiers(&[2 * SCALE, SCALE]);
+        repeated.tiers[1].label = String::from("TIER-0");
+        assert!(!validate_label_tiers(&repeated));
+    }
+
+    #[test]
+    fn burn_fraction_requires_burn_hotkey() {
+        assert!(validate_scoring_config(&ScoringConfig::default()));
+        assert!(validate_scoring_config(&burn_config("burn", SCALE / 2)));
+        assert!(validate_scoring_config(&burn_config("burn", 0)));
+        assert!(!validate_scoring_config(&burn_config("", 0)));
+        assert!(!validate_scoring_config(&ScoringConfig {
+            burn_fraction: SCALE / 2,
+            ..ScoringConfig::default()
+        }));
+    }
 }
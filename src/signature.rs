@@ -0,0 +1,340 @@
+//! sr25519 verification of signed miner actions. Every action has a
+//! canonical message of the form `{action}:{data}:{timestamp}` that the
+//! module rebuilds from the request, so a signature only authorizes the
+//! exact payload it was made over.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use blake2::{Blake2b512, Digest};
+use platform_challenge_sdk_wasm::host_functions::host_get_timestamp;
+use schnorrkel::{PublicKey, Signature};
+
+use crate::storage;
+use crate::types::{
+    AuthConfig, BountySubmission, MultiRepoSubmission, RegisterRequest, SeenSignature,
+};
+
+const SIGNING_CONTEXT: &[u8] = b"substrate";
+const SS58_PREFIX: &[u8] = b"SS58PRE";
+const SS58_CHECKSUM_LEN: usize = 2;
+const PUBLIC_KEY_LEN: usize = 32;
+const MILLIS_THRESHOLD: i64 = 100_000_000_000;
+
+/// Decodes an SS58 address into its 32-byte public key, checking the
+/// checksum. Both one- and two-byte network prefixes are accepted.
+pub fn decode_ss58(address: &str) -> Option<[u8; PUBLIC_KEY_LEN]> {
+    let data = bs58::decode(address).into_vec().ok()?;
+    let prefix_len = match data.first()? {
+        0..=63 => 1,
+        64..=127 => 2,
+        _ => return None,
+    };
+    if data.len() != prefix_len + PUBLIC_KEY_LEN + SS58_CHECKSUM_LEN {
+        return None;
+    }
+
+    let (body, checksum) = data.split_at(prefix_len + PUBLIC_KEY_LEN);
+    let mut hasher = Blake2b512::new();
+    hasher.update(SS58_PREFIX);
+    hasher.update(body);
+    if hasher.finalize()[..SS58_CHECKSUM_LEN] != *checksum {
+        return None;
+    }
+
+    let mut key = [0u8; PUBLIC_KEY_LEN];
+    key.copy_from_slice(&body[prefix_len..]);
+    Some(key)
+}
+
+/// Verifies `signature` over `message` for the SS58 `hotkey`. Messages
+/// signed through wallet extensions arrive wrapped in `<Bytes>...</Bytes>`,
+/// so the wrapped form is accepted as well.
+pub fn verify_sr25519(hotkey: &str, message: &[u8], signature: &[u8]) -> bool {
+    let public_key = match decode_ss58(hotkey).and_then(|k| PublicKey::from_bytes(&k).ok()) {
+        Some(k) => k,
+        None => return false,
+    };
+    let signature = match Signature::from_bytes(signature) {
+        Ok(s) => s,
+        Err(_) => return false,
+    };
+
+    if public_key
+        .verify_simple(SIGNING_CONTEXT, message, &signature)
+        .is_ok()
+    {
+        return true;
+    }
+
+    let mut wrapped = Vec::with_capacity(message.len() + 15);
+    wrapped.extend_from_slice(b"<Bytes>");
+    wrapped.extend_from_slice(message);
+    wrapped.extend_from_slice(b"</Bytes>");
+    public_key
+        .verify_simple(SIGNING_CONTEXT, &wrapped, &signature)
+        .is_ok()
+}
+
+pub fn register_message(github_username: &str, timestamp: i64) -> String {
+    alloc::format!(
+        "register_github:{}:{}",
+        github_username.to_lowercase(),
+        timestamp
+    )
+}
+
+/// `claim_bounty:{username}:{owner}/{repo}:{n1},{n2},...:{timestamp}`, with
+/// issue numbers in submission order.
+pub fn claim_message(submission: &BountySubmission) -> String {
+    let issues: Vec<String> = submission
+        .issue_numbers
+        .iter()
+        .map(|n| alloc::format!("{}", n))
+        .collect();
+    alloc::format!(
+        "claim_bounty:{}:{}/{}:{}:{}",
+        submission.github_username.to_lowercase(),
+        submission.repo_owner,
+        submission.repo_name,
+        issues.join(","),
+        submission.timestamp
+    )
+}
+
+/// `claim_bounty_multi:{username}:{owner}/{repo}#{issue},...:{timestamp}`,
+/// with targets in submission order.
+pub fn multi_claim_message(submission: &MultiRepoSubmission) -> String {
+    let targets: Vec<String> = submission
+        .targets
+        .iter()
+        .map(|t| alloc::format!("{}/{}#{}", t.repo_owner, t.repo_name, t.issue_number))
+        .collect();
+    alloc::format!(
+        "claim_bounty_multi:{}:{}:{}",
+        submission.github_username.to_lowercase(),
+        targets.join(","),
+        submission.timestamp
+    )
+}
+
+pub fn verify_registration(request: &RegisterRequest, hotkey: &str) -> bool {
+    let message = register_message(&request.github_username, request.timestamp);
+    verify_sr25519(hotkey, message.as_bytes(), &request.signature)
+}
+
+pub fn verify_multi_submission(submission: &MultiRepoSubmission) -> bool {
+    let message = multi_claim_message(submission);
+    verify_sr25519(
+        &submission.hotkey,
+        message.as_bytes(),
+        &submission.signature,
+    )
+}
+
+pub fn verify_submission(submission: &BountySubmission) -> bool {
+    let message = claim_message(submission);
+    verify_sr25519(
+        &submission.hotkey,
+        message.as_bytes(),
+        &submission.signature,
+    )
+}
+
+/// Host clock in Unix seconds. A host reporting milliseconds is recognized
+/// by magnitude, since second timestamps stay below the threshold until the
+/// year 5138.
+pub fn host_now_secs() -> i64 {
+    let now = host_get_timestamp();
+    if now >= MILLIS_THRESHOLD {
+        now / 1000
+    } else {
+        now
+    }
+}
+
+pub fn is_timestamp_fresh(timestamp: i64, config: &AuthConfig) -> bool {
+    timestamp.abs_diff(host_now_secs()) <= config.timestamp_window_secs
+}
+
+/// Marks `signature` as used by `hotkey`. Fails if it was used before or if
+/// the hotkey already has `replay_cache_size` signatures inside the window.
+/// Entries whose timestamps left the window are dropped first, since those
+/// payloads can no longer pass `is_timestamp_fresh`.
+pub fn consume_signature(
+    hotkey: &str,
+    timestamp: i64,
+    signature: &[u8],
+    config: &AuthConfig,
+) -> bool {
+    let mut seen = storage::get_seen_signatures(hotkey);
+    seen.retain(|s| is_timestamp_fresh(s.timestamp, config));
+
+    if seen.iter().any(|s| s.signature == signature) {
+        return false;
+    }
+    if seen.len() >= config.replay_cache_size as usize {
+        return false;
+    }
+
+    seen.push(SeenSignature {
+        timestamp,
+        signature: Vec::from(signature),
+    });
+    storage::store_seen_signatures(hotkey, &seen)
+}
+
+pub fn validate_auth_config(config: &AuthConfig) -> bool {
+    config.timestamp_window_secs > 0 && config.replay_cache_size > 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ClaimTarget;
+    use alloc::vec;
+
+    const ALICE: &str = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+    const ALICE_POLKADOT: &str = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+    const ALICE_PREFIX_1284: &str = "VdvKmYJfD4VXA9fzz1SbmCo2eYHSzUFbaDCZSuaNKJAe8YNg6";
+    const BOB: &str = "5FHneW46xGXgs5mUiveU4sbTyGBzmstUspZC92UhjJM694ty";
+    const ALICE_PUBLIC: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    const TIMESTAMP: i64 = 1_705_590_000;
+    /// Alice's signature over `register_github:alice:1705590000`.
+    const REGISTER_SIG: &str = "e0f1e3db548668837a77bcd1f3949bf190e54aa397d3e36143c2a5929815b9184a7560f9ba949159034bef4767234a0b5692c963dd1ec9f3c84b72d5e603bf85";
+    /// Alice's signature over the same message wrapped in `<Bytes>`.
+    const REGISTER_WRAPPED_SIG: &str = "fa028ba950ed9cc957040c9f7b6b84d0424004c9c81b31a026744ccc7ac2b973dcf00397ed150d0b0f33895af1419925dddcf2226e4df1d5ad3779bba8d55181";
+    /// Alice's signature over
+    /// `claim_bounty:alice:PlatformNetwork/bounty-challenge:42,43:1705590000`.
+    const CLAIM_SIG: &str = "3ef1ac3b51248154a703264f9f99a35107bf48a6fe5cdea512c76764991a2178479b5052e25373e7e00aaf1028d89a9999a404ecc4d84d1d57aecc0e4783b48d";
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn submission(hotkey: &str, signature: &str) -> BountySubmission {
+        BountySubmission {
+            hotkey: String::from(hotkey),
+            github_username: String::from("Alice"),
+            issue_numbers: vec![42, 43],
+            repo_owner: String::from("PlatformNetwork"),
+            repo_name: String::from("bounty-challenge"),
+            signature: hex(signature),
+            timestamp: TIMESTAMP,
+        }
+    }
+
+    #[test]
+    fn decode_ss58_one_byte_prefix() {
+        assert_eq!(decode_ss58(ALICE).unwrap().as_slice(), hex(ALICE_PUBLIC));
+        assert_eq!(
+            decode_ss58(ALICE_POLKADOT).unwrap().as_slice(),
+            hex(ALICE_PUBLIC)
+        );
+    }
+
+    #[test]
+    fn decode_ss58_two_byte_prefix() {
+        assert_eq!(
+            decode_ss58(ALICE_PREFIX_1284).unwrap().as_slice(),
+            hex(ALICE_PUBLIC)
+        );
+    }
+
+    #[test]
+    fn decode_ss58_rejects_bad_checksum_and_garbage() {
+        let mut tampered = String::from(&ALICE[..ALICE.len() - 1]);
+        tampered.push('Z');
+        assert!(decode_ss58(&tampered).is_none());
+        assert!(decode_ss58(&ALICE[..ALICE.len() - 4]).is_none());
+        assert!(decode_ss58("not-an-address").is_none());
+        assert!(decode_ss58("").is_none());
+    }
+
+    #[test]
+    fn canonical_messages() {
+        assert_eq!(
+            register_message("Alice", TIMESTAMP),
+            "register_github:alice:1705590000"
+        );
+        assert_eq!(
+            claim_message(&submission(ALICE, CLAIM_SIG)),
+            "claim_bounty:alice:PlatformNetwork/bounty-challenge:42,43:1705590000"
+        );
+
+        let multi = MultiRepoSubmission {
+            hotkey: String::from(ALICE),
+            github_username: String::from("Alice"),
+            targets: vec![
+                ClaimTarget {
+                    repo_owner: String::from("PlatformNetwork"),
+                    repo_name: String::from("bounty-challenge"),
+                    issue_number: 42,
+                },
+                ClaimTarget {
+                    repo_owner: String::from("CortexLM"),
+                    repo_name: String::from("cortex"),
+                    issue_number: 7,
+                },
+            ],
+            signature: Vec::new(),
+            timestamp: TIMESTAMP,
+        };
+        assert_eq!(
+            multi_claim_message(&multi),
+            "claim_bounty_multi:alice:PlatformNetwork/bounty-challenge#42,CortexLM/cortex#7:1705590000"
+        );
+    }
+
+    #[test]
+    fn verify_sr25519_plain_message() {
+        let message = register_message("Alice", TIMESTAMP);
+        assert!(verify_sr25519(
+            ALICE,
+            message.as_bytes(),
+            &hex(REGISTER_SIG)
+        ));
+        assert!(verify_sr25519(
+            ALICE_PREFIX_1284,
+            message.as_bytes(),
+            &hex(REGISTER_SIG)
+        ));
+
+        let other = register_message("Alice", TIMESTAMP + 1);
+        assert!(!verify_sr25519(ALICE, other.as_bytes(), &hex(REGISTER_SIG)));
+        assert!(!verify_sr25519(BOB, message.as_bytes(), &hex(REGISTER_SIG)));
+        assert!(!verify_sr25519(ALICE, message.as_bytes(), &[0u8; 64]));
+        assert!(!verify_sr25519(ALICE, message.as_bytes(), &[]));
+    }
+
+    #[test]
+    fn verify_sr25519_bytes_wrapped_message() {
+        let message = register_message("Alice", TIMESTAMP);
+        assert!(verify_sr25519(
+            ALICE,
+            message.as_bytes(),
+            &hex(REGISTER_WRAPPED_SIG)
+        ));
+    }
+
+    #[test]
+    fn verify_registration_and_submission() {
+        let request = RegisterRequest {
+            hotkey: String::from(ALICE),
+            github_username: String::from("alice"),
+            signature: hex(REGISTER_SIG),
+            timestamp: TIMESTAMP,
+        };
+        assert!(verify_registration(&request, ALICE));
+        assert!(!verify_registration(&request, BOB));
+
+        assert!(verify_submission(&submission(ALICE, CLAIM_SIG)));
+        let mut reordered = submission(ALICE, CLAIM_SIG);
+        reordered.issue_numbers = vec![43, 42];
+        assert!(!verify_submission(&reordered));
+        assert!(!verify_submission(&submission(BOB, CLAIM_SIG)));
+    }
+}
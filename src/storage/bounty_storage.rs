@@ -3,11 +3,23 @@ use alloc::vec::Vec;
 use platform_challenge_sdk_wasm::host_functions::{
     host_consensus_get_epoch, host_storage_get, host_storage_set,
 };
+use serde::Deserialize;
 
+use crate::fingerprint::Fingerprint;
+use crate::fixed::{self, Ppb};
 use crate::types::{
-    InvalidIssueRecord, IssueRecord, LeaderboardEntry, UserBalance, UserRegistration,
+    AuthConfig, ClaimActivity, ClaimLimits, CreditedIssue, DecayConfig, DuplicateConfig,
+    DuplicateLink, EpochClock, InvalidIssueRecord, IssueContent, IssueRecord, LabelTiers,
+    LeaderboardEntry, PendingAssessment, PointMultipliers, QualityConfig, QualityReview,
+    RankHistoryEntry, RepositoryRegistry, ScoringConfig, SeenSignature, StarTarget, StarTargets,
+    UserBalance, UserRegistration, ValidatorSet, WeightEmaState,
 };
 
+const LEADERBOARD_SNAPSHOT_RETENTION: usize = 180;
+const DUPLICATE_LINK_RETENTION: usize = 1000;
+const QUALITY_REVIEW_RETENTION: usize = 1000;
+const QUALITY_QUEUE_RETENTION: usize = 1000;
+
 fn make_key(prefix: &[u8], suffix: &str) -> Vec<u8> {
     let mut key = Vec::from(prefix);
     key.extend_from_slice(suffix.as_bytes());
@@ -24,6 +36,24 @@ fn issue_key(repo_owner: &str, repo_name: &str, issue_number: u32) -> Vec<u8> {
     key
 }
 
+fn content_key(repo_owner: &str, repo_name: &str, issue_number: u32) -> Vec<u8> {
+    let mut key = Vec::from(b"issue_content:" as &[u8]);
+    key.extend_from_slice(repo_owner.as_bytes());
+    key.push(b'/');
+    key.extend_from_slice(repo_name.as_bytes());
+    key.push(b':');
+    key.extend_from_slice(&issue_number.to_le_bytes());
+    key
+}
+
+fn fingerprint_key(repo_owner: &str, repo_name: &str) -> Vec<u8> {
+    let mut key = Vec::from(b"fingerprints:" as &[u8]);
+    key.extend_from_slice(repo_owner.as_bytes());
+    key.push(b'/');
+    key.extend_from_slice(repo_name.as_bytes());
+    key
+}
+
 pub fn register_user(github_username: &str, hotkey: &str) -> bool {
     let existing_hotkey = get_hotkey_by_github(github_username);
     if let Some(ref existing) = existing_hotkey {
@@ -89,14 +119,9 @@ pub fn get_github_by_hotkey(hotkey: &str) -> Option<String> {
     Some(reg.github_username)
 }
 
-pub fn record_valid_issue(
-    issue_number: u32,
-    repo_owner: &str,
-    repo_name: &str,
-    author: &str,
-    hotkey: &str,
-) -> bool {
-    let key = issue_key(repo_owner, repo_name, issue_number);
+/// Stores `issue` as claimed by `hotkey` and credits it with `points`.
+pub fn record_valid_issue(issue: &IssueRecord, hotkey: &str, points: Ppb) -> bool {
+    let key = issue_key(&issue.repo_owner, &issue.repo_name, issue.issue_number);
 
     if let Ok(data) = host_storage_get(&key) {
         if !data.is_empty() {
@@ -108,15 +133,12 @@ pub fn record_valid_issue(
     let current_epoch = if epoch >= 0 { epoch as u64 } else { 0 };
 
     let record = IssueRecord {
-        issue_number,
-        repo_owner: String::from(repo_owner),
-        repo_name: String::from(repo_name),
-        author: String::from(author),
         is_closed: true,
         has_valid_label: true,
         has_invalid_label: false,
         claimed_by_hotkey: Some(String::from(hotkey)),
         recorded_epoch: current_epoch,
+        ..issue.clone()
     };
 
     let data = match bincode::serialize(&record) {
@@ -128,10 +150,79 @@ pub fn record_valid_issue(
         return false;
     }
 
-    increment_valid_count(hotkey);
+    add_credited_issue(
+        hotkey,
+        CreditedIssue {
+            issue_number: issue.issue_number,
+            repo_owner: issue.repo_owner.clone(),
+            repo_name: issue.repo_name.clone(),
+            recorded_epoch: current_epoch,
+            points,
+        },
+    );
+    if let Some(fingerprint) = issue.fingerprint {
+        add_issue_fingerprint(
+            &issue.repo_owner,
+            &issue.repo_name,
+            issue.issue_number,
+            fingerprint,
+        );
+    }
+    increment_valid_count(hotkey, points);
     true
 }
 
+/// Fingerprints of the credited issues in a repository, as
+/// `(issue_number, fingerprint)` in the order they were credited.
+pub fn get_issue_fingerprints(repo_owner: &str, repo_name: &str) -> Vec<(u32, Fingerprint)> {
+    host_storage_get(&fingerprint_key(repo_owner, repo_name))
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+fn add_issue_fingerprint(
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u32,
+    fingerprint: Fingerprint,
+) {
+    let mut fingerprints = get_issue_fingerprints(repo_owner, repo_name);
+    fingerprints.push((issue_number, fingerprint));
+    if let Ok(data) = bincode::serialize(&fingerprints) {
+        let _ = host_storage_set(&fingerprint_key(repo_owner, repo_name), &data);
+    }
+}
+
+pub fn get_credited_issues(hotkey: &str) -> Vec<CreditedIssue> {
+    let key = make_key(b"hotkey_issues:", hotkey);
+    host_storage_get(&key)
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+fn add_credited_issue(hotkey: &str, issue: CreditedIssue) {
+    let mut issues = get_credited_issues(hotkey);
+    issues.push(issue);
+    let key = make_key(b"hotkey_issues:", hotkey);
+    if let Ok(data) = bincode::serialize(&issues) {
+        let _ = host_storage_set(&key, &data);
+    }
+}
+
 pub fn record_invalid_issue(
     issue_number: u32,
     repo_owner: &str,
@@ -195,6 +286,42 @@ pub fn get_issue_record(
     bincode::deserialize(&data).ok()
 }
 
+/// `UserBalance` as stored before `valid_points`, when every valid issue was
+/// worth one point.
+#[derive(Deserialize)]
+struct LegacyUserBalance {
+    valid_count: u32,
+    invalid_count: u32,
+    duplicate_count: u32,
+    star_count: u32,
+    is_penalized: bool,
+}
+
+impl From<LegacyUserBalance> for UserBalance {
+    fn from(legacy: LegacyUserBalance) -> Self {
+        Self {
+            valid_count: legacy.valid_count,
+            valid_points: fixed::from_int(legacy.valid_count as u64),
+            invalid_count: legacy.invalid_count,
+            duplicate_count: legacy.duplicate_count,
+            star_count: legacy.star_count,
+            is_penalized: legacy.is_penalized,
+        }
+    }
+}
+
+/// Decodes a stored balance, falling back to the legacy layout. A legacy
+/// balance is shorter than the current one, so it never decodes as current.
+fn decode_user_balance(data: &[u8]) -> Option<UserBalance> {
+    bincode::deserialize(data).ok().or_else(|| {
+        bincode::deserialize::<LegacyUserBalance>(data)
+            .ok()
+            .map(UserBalance::from)
+    })
+}
+
+/// Balances in the legacy layout are converted on read and rewritten in the
+/// current layout on their next update.
 pub fn get_user_balance(hotkey: &str) -> UserBalance {
     let key = make_key(b"balance:", hotkey);
     host_storage_get(&key)
@@ -203,7 +330,7 @@ pub fn get_user_balance(hotkey: &str) -> UserBalance {
             if d.is_empty() {
                 None
             } else {
-                bincode::deserialize(&d).ok()
+                decode_user_balance(&d)
             }
         })
         .unwrap_or_default()
@@ -216,21 +343,26 @@ fn store_user_balance(hotkey: &str, balance: &UserBalance) {
     }
 }
 
-fn increment_valid_count(hotkey: &str) {
+fn increment_valid_count(hotkey: &str, points: Ppb) {
     let mut balance = get_user_balance(hotkey);
     balance.valid_count = balance.valid_count.saturating_add(1);
+    balance.valid_points = balance.valid_points.saturating_add(points);
     store_user_balance(hotkey, &balance);
 }
 
 fn increment_invalid_count(hotkey: &str) {
     let mut balance = get_user_balance(hotkey);
     balance.invalid_count = balance.invalid_count.saturating_add(1);
+    balance.is_penalized = is_balance_penalized(&balance);
+    store_user_balance(hotkey, &balance);
+}
+
+pub fn is_balance_penalized(balance: &UserBalance) -> bool {
     let penalty = balance
         .invalid_count
         .saturating_sub(balance.valid_count)
         .saturating_add(balance.duplicate_count.saturating_sub(balance.valid_count));
-    balance.is_penalized = penalty > 0;
-    store_user_balance(hotkey, &balance);
+    penalty > 0
 }
 
 pub fn get_leaderboard() -> Vec<LeaderboardEntry> {
@@ -246,13 +378,113 @@ pub fn get_leaderboard() -> Vec<LeaderboardEntry> {
         .unwrap_or_default()
 }
 
+/// Stores the live leaderboard. When the epoch advances, the previous
+/// leaderboard is first kept as that epoch's immutable snapshot.
 pub fn store_leaderboard(entries: &[LeaderboardEntry]) -> bool {
+    if let Some(new_epoch) = entries.first().map(|e| e.last_epoch) {
+        let previous = get_leaderboard();
+        if let Some(previous_epoch) = previous.first().map(|e| e.last_epoch) {
+            if previous_epoch < new_epoch {
+                store_leaderboard_snapshot(previous_epoch, &previous);
+            }
+        }
+    }
+
     if let Ok(data) = bincode::serialize(entries) {
         return host_storage_set(b"leaderboard", &data).is_ok();
     }
     false
 }
 
+fn snapshot_key(epoch: u64) -> Vec<u8> {
+    let mut key = Vec::from(b"leaderboard_snapshot:" as &[u8]);
+    key.extend_from_slice(&epoch.to_le_bytes());
+    key
+}
+
+pub fn get_snapshot_epochs() -> Vec<u64> {
+    host_storage_get(b"leaderboard_snapshot_epochs")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn get_leaderboard_snapshot(epoch: u64) -> Option<Vec<LeaderboardEntry>> {
+    let data = host_storage_get(&snapshot_key(epoch)).ok()?;
+    if data.is_empty() {
+        return None;
+    }
+    bincode::deserialize(&data).ok()
+}
+
+fn store_leaderboard_snapshot(epoch: u64, entries: &[LeaderboardEntry]) {
+    let mut epochs = get_snapshot_epochs();
+    if epochs.contains(&epoch) {
+        return;
+    }
+
+    let data = match bincode::serialize(entries) {
+        Ok(d) => d,
+        Err(_) => return,
+    };
+    if host_storage_set(&snapshot_key(epoch), &data).is_err() {
+        return;
+    }
+
+    epochs.push(epoch);
+    epochs.sort_unstable();
+    while epochs.len() > LEADERBOARD_SNAPSHOT_RETENTION {
+        let oldest = epochs.remove(0);
+        let _ = host_storage_set(&snapshot_key(oldest), &[]);
+    }
+    if let Ok(data) = bincode::serialize(&epochs) {
+        let _ = host_storage_set(b"leaderboard_snapshot_epochs", &data);
+    }
+
+    let oldest = epochs.first().copied().unwrap_or(epoch);
+    for entry in entries {
+        add_rank_history(entry, epoch, oldest);
+    }
+}
+
+/// Ranks of `hotkey` in each retained snapshot, oldest first.
+pub fn get_rank_history(hotkey: &str) -> Vec<RankHistoryEntry> {
+    let key = make_key(b"rank_history:", hotkey);
+    host_storage_get(&key)
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Appends the hotkey's standing in the snapshot for `epoch`, dropping
+/// entries for epochs whose snapshot is no longer retained.
+fn add_rank_history(entry: &LeaderboardEntry, epoch: u64, oldest_epoch: u64) {
+    let mut history = get_rank_history(&entry.hotkey);
+    history.retain(|h| h.epoch >= oldest_epoch && h.epoch != epoch);
+    history.push(RankHistoryEntry {
+        epoch,
+        rank: entry.rank,
+        score: entry.score,
+        net_points: entry.net_points,
+    });
+    let key = make_key(b"rank_history:", &entry.hotkey);
+    if let Ok(data) = bincode::serialize(&history) {
+        let _ = host_storage_set(&key, &data);
+    }
+}
+
 pub fn get_registered_hotkeys() -> Vec<String> {
     host_storage_get(b"registered_hotkeys")
         .ok()
@@ -323,25 +555,560 @@ pub fn get_active_miner_count() -> u64 {
         .unwrap_or(0)
 }
 
-pub fn store_validator_count(count: u64) {
-    let _ = host_storage_set(b"validator_count", &count.to_le_bytes());
+pub fn get_validator_set() -> ValidatorSet {
+    host_storage_get(b"validator_set")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_validator_set(validators: &ValidatorSet) -> bool {
+    if let Ok(data) = bincode::serialize(validators) {
+        return host_storage_set(b"validator_set", &data).is_ok();
+    }
+    false
 }
 
 pub fn get_validator_count() -> u64 {
-    host_storage_get(b"validator_count")
+    get_validator_set().hotkeys.len() as u64
+}
+
+pub fn get_scoring_config() -> ScoringConfig {
+    host_storage_get(b"scoring_config")
         .ok()
         .and_then(|d| {
-            if d.len() >= 8 {
-                let mut buf = [0u8; 8];
-                buf.copy_from_slice(&d[..8]);
-                Some(u64::from_le_bytes(buf))
+            if d.is_empty() {
+                None
             } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_scoring_config(config: &ScoringConfig) -> bool {
+    if let Ok(data) = bincode::serialize(config) {
+        return host_storage_set(b"scoring_config", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_weight_ema() -> WeightEmaState {
+    host_storage_get(b"weight_ema")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
                 None
+            } else {
+                bincode::deserialize(&d).ok()
             }
         })
-        .unwrap_or(0)
+        .unwrap_or_default()
+}
+
+pub fn store_weight_ema(state: &WeightEmaState) -> bool {
+    if let Ok(data) = bincode::serialize(state) {
+        return host_storage_set(b"weight_ema", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_decay_config() -> DecayConfig {
+    host_storage_get(b"decay_config")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_decay_config(config: &DecayConfig) -> bool {
+    if let Ok(data) = bincode::serialize(config) {
+        return host_storage_set(b"decay_config", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_point_multipliers() -> PointMultipliers {
+    host_storage_get(b"point_multipliers")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_point_multipliers(multipliers: &PointMultipliers) -> bool {
+    if let Ok(data) = bincode::serialize(multipliers) {
+        return host_storage_set(b"point_multipliers", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_label_tiers() -> LabelTiers {
+    host_storage_get(b"label_tiers")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_label_tiers(tiers: &LabelTiers) -> bool {
+    if let Ok(data) = bincode::serialize(tiers) {
+        return host_storage_set(b"label_tiers", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_auth_config() -> AuthConfig {
+    host_storage_get(b"auth_config")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_auth_config(config: &AuthConfig) -> bool {
+    if let Ok(data) = bincode::serialize(config) {
+        return host_storage_set(b"auth_config", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_claim_limits() -> ClaimLimits {
+    host_storage_get(b"claim_limits")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_claim_limits(limits: &ClaimLimits) -> bool {
+    if let Ok(data) = bincode::serialize(limits) {
+        return host_storage_set(b"claim_limits", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_duplicate_config() -> DuplicateConfig {
+    host_storage_get(b"duplicate_config")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_duplicate_config(config: &DuplicateConfig) -> bool {
+    if let Ok(data) = bincode::serialize(config) {
+        return host_storage_set(b"duplicate_config", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_duplicate_links() -> Vec<DuplicateLink> {
+    host_storage_get(b"duplicate_links")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Records `link` unless the issue already has one, keeping the most recent
+/// `DUPLICATE_LINK_RETENTION` links.
+pub fn add_duplicate_link(link: DuplicateLink) {
+    let mut links = get_duplicate_links();
+    if links.iter().any(|l| {
+        l.issue_number == link.issue_number
+            && l.repo_owner == link.repo_owner
+            && l.repo_name == link.repo_name
+    }) {
+        return;
+    }
+    links.push(link);
+    if links.len() > DUPLICATE_LINK_RETENTION {
+        links.drain(..links.len() - DUPLICATE_LINK_RETENTION);
+    }
+    if let Ok(data) = bincode::serialize(&links) {
+        let _ = host_storage_set(b"duplicate_links", &data);
+    }
+}
+
+pub fn get_quality_config() -> QualityConfig {
+    host_storage_get(b"quality_config")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_quality_config(config: &QualityConfig) -> bool {
+    if let Ok(data) = bincode::serialize(config) {
+        return host_storage_set(b"quality_config", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_issue_content(
+    repo_owner: &str,
+    repo_name: &str,
+    issue_number: u32,
+) -> Option<IssueContent> {
+    host_storage_get(&content_key(repo_owner, repo_name, issue_number))
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+}
+
+pub fn store_issue_content(content: &IssueContent) -> bool {
+    let key = content_key(
+        &content.repo_owner,
+        &content.repo_name,
+        content.issue_number,
+    );
+    if let Ok(data) = bincode::serialize(content) {
+        return host_storage_set(&key, &data).is_ok();
+    }
+    false
+}
+
+pub fn get_quality_reviews() -> Vec<QualityReview> {
+    host_storage_get(b"quality_reviews")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Records `review` unless the issue already has one, keeping the most
+/// recent `QUALITY_REVIEW_RETENTION` reviews.
+pub fn add_quality_review(review: QualityReview) {
+    let mut reviews = get_quality_reviews();
+    if reviews.iter().any(|r| {
+        r.issue_number == review.issue_number
+            && r.repo_owner == review.repo_owner
+            && r.repo_name == review.repo_name
+    }) {
+        return;
+    }
+    reviews.push(review);
+    if reviews.len() > QUALITY_REVIEW_RETENTION {
+        reviews.drain(..reviews.len() - QUALITY_REVIEW_RETENTION);
+    }
+    if let Ok(data) = bincode::serialize(&reviews) {
+        let _ = host_storage_set(b"quality_reviews", &data);
+    }
+}
+
+pub fn get_quality_queue() -> Vec<PendingAssessment> {
+    host_storage_get(b"quality_queue")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_quality_queue(queue: &[PendingAssessment]) -> bool {
+    if let Ok(data) = bincode::serialize(queue) {
+        return host_storage_set(b"quality_queue", &data).is_ok();
+    }
+    false
+}
+
+/// Queues `pending` for assessment unless it is already queued, keeping the
+/// most recent `QUALITY_QUEUE_RETENTION` entries.
+pub fn queue_assessment(pending: PendingAssessment) {
+    let mut queue = get_quality_queue();
+    if queue.iter().any(|p| {
+        p.issue_number == pending.issue_number
+            && p.repo_owner == pending.repo_owner
+            && p.repo_name == pending.repo_name
+    }) {
+        return;
+    }
+    queue.push(pending);
+    if queue.len() > QUALITY_QUEUE_RETENTION {
+        queue.drain(..queue.len() - QUALITY_QUEUE_RETENTION);
+    }
+    store_quality_queue(&queue);
+}
+
+pub fn get_claim_activity(hotkey: &str) -> ClaimActivity {
+    let key = make_key(b"claim_activity:", hotkey);
+    host_storage_get(&key)
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_claim_activity(hotkey: &str, activity: &ClaimActivity) -> bool {
+    let key = make_key(b"claim_activity:", hotkey);
+    if let Ok(data) = bincode::serialize(activity) {
+        return host_storage_set(&key, &data).is_ok();
+    }
+    false
+}
+
+pub fn get_seen_signatures(hotkey: &str) -> Vec<SeenSignature> {
+    let key = make_key(b"seen_signatures:", hotkey);
+    host_storage_get(&key)
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_seen_signatures(hotkey: &str, seen: &[SeenSignature]) -> bool {
+    let key = make_key(b"seen_signatures:", hotkey);
+    if let Ok(data) = bincode::serialize(seen) {
+        return host_storage_set(&key, &data).is_ok();
+    }
+    false
+}
+
+pub fn get_repo_registry() -> RepositoryRegistry {
+    host_storage_get(b"repo_registry")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_repo_registry(registry: &RepositoryRegistry) -> bool {
+    if let Ok(data) = bincode::serialize(registry) {
+        return host_storage_set(b"repo_registry", &data).is_ok();
+    }
+    false
+}
+
+pub fn get_star_targets() -> StarTargets {
+    host_storage_get(b"star_targets")
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+pub fn store_star_targets(targets: &StarTargets) -> bool {
+    if let Ok(data) = bincode::serialize(targets) {
+        return host_storage_set(b"star_targets", &data).is_ok();
+    }
+    false
+}
+
+/// Keeps only the entries of `starred` that are current star targets,
+/// spelled as in the target list, sorted and deduplicated.
+pub fn normalize_starred(targets: &StarTargets, starred: &[StarTarget]) -> Vec<StarTarget> {
+    let mut normalized: Vec<StarTarget> = targets
+        .repos
+        .iter()
+        .filter(|t| starred.iter().any(|s| s.matches(t)))
+        .cloned()
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+pub fn get_verified_stars(hotkey: &str) -> Vec<StarTarget> {
+    let key = make_key(b"stars:", hotkey);
+    host_storage_get(&key)
+        .ok()
+        .and_then(|d| {
+            if d.is_empty() {
+                None
+            } else {
+                bincode::deserialize(&d).ok()
+            }
+        })
+        .unwrap_or_default()
+}
+
+/// Stores the consensus starred set for `hotkey` and sets its `star_count`
+/// to the number of those repositories that are still star targets.
+pub fn set_verified_stars(hotkey: &str, starred: &[StarTarget], targets: &StarTargets) -> bool {
+    let key = make_key(b"stars:", hotkey);
+    let data = match bincode::serialize(starred) {
+        Ok(d) => d,
+        Err(_) => return false,
+    };
+    if host_storage_set(&key, &data).is_err() {
+        return false;
+    }
+
+    let mut balance = get_user_balance(hotkey);
+    balance.star_count = normalize_starred(targets, starred).len() as u32;
+    store_user_balance(hotkey, &balance);
+    true
+}
+
+/// Host time of the hotkey's first registration. Registrations made before
+/// this was recorded have none.
+pub fn get_registered_at(hotkey: &str) -> Option<i64> {
+    let key = make_key(b"registered_at:", hotkey);
+    host_storage_get(&key).ok().and_then(|d| {
+        if d.is_empty() {
+            None
+        } else {
+            bincode::deserialize(&d).ok()
+        }
+    })
+}
+
+pub fn record_registered_at(hotkey: &str, timestamp: i64) {
+    if get_registered_at(hotkey).is_some() {
+        return;
+    }
+    let key = make_key(b"registered_at:", hotkey);
+    if let Ok(data) = bincode::serialize(&timestamp) {
+        let _ = host_storage_set(&key, &data);
+    }
+}
+
+pub fn get_epoch_clock() -> Option<EpochClock> {
+    host_storage_get(b"epoch_clock").ok().and_then(|d| {
+        if d.is_empty() {
+            None
+        } else {
+            bincode::deserialize(&d).ok()
+        }
+    })
+}
+
+/// Records `now` as the time `epoch` was first seen if it is later than any
+/// epoch seen before.
+pub fn observe_epoch(epoch: u64, now: i64) {
+    let clock = match get_epoch_clock() {
+        None => EpochClock {
+            first_epoch: epoch,
+            first_seen_at: now,
+            last_epoch: epoch,
+            last_seen_at: now,
+        },
+        Some(clock) if epoch > clock.last_epoch => EpochClock {
+            last_epoch: epoch,
+            last_seen_at: now,
+            ..clock
+        },
+        Some(_) => return,
+    };
+    if let Ok(data) = bincode::serialize(&clock) {
+        let _ = host_storage_set(b"epoch_clock", &data);
+    }
 }
 
 pub fn ensure_hotkey_tracked(hotkey: &str) {
     add_registered_hotkey(hotkey);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_legacy_user_balance() {
+        let legacy = bincode::serialize(&(3u32, 1u32, 2u32, 4u32, true)).unwrap();
+        let balance = decode_user_balance(&legacy).unwrap();
+        assert_eq!(balance.valid_count, 3);
+        assert_eq!(balance.valid_points, fixed::from_int(3));
+        assert_eq!(balance.invalid_count, 1);
+        assert_eq!(balance.duplicate_count, 2);
+        assert_eq!(balance.star_count, 4);
+        assert!(balance.is_penalized);
+    }
+
+    #[test]
+    fn decodes_current_user_balance() {
+        let current = UserBalance {
+            valid_count: 3,
+            valid_points: 4_500_000_000,
+            invalid_count: 1,
+            duplicate_count: 0,
+            star_count: 2,
+            is_penalized: false,
+        };
+        let balance = decode_user_balance(&bincode::serialize(&current).unwrap()).unwrap();
+        assert_eq!(balance.valid_points, 4_500_000_000);
+        assert_eq!(balance.star_count, 2);
+        assert!(!balance.is_penalized);
+    }
+}
@@ -2,6 +2,9 @@ use alloc::string::String;
 use alloc::vec::Vec;
 use serde::{Deserialize, Serialize};
 
+use crate::fingerprint::Fingerprint;
+use crate::fixed::{Ppb, SCALE};
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct BountySubmission {
     pub hotkey: String,
@@ -13,6 +16,44 @@ pub struct BountySubmission {
     pub timestamp: i64,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClaimTarget {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub issue_number: u32,
+}
+
+/// Claim covering issues across any number of repositories.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MultiRepoSubmission {
+    pub hotkey: String,
+    pub github_username: String,
+    pub targets: Vec<ClaimTarget>,
+    pub signature: Vec<u8>,
+    pub timestamp: i64,
+}
+
+impl From<BountySubmission> for MultiRepoSubmission {
+    fn from(submission: BountySubmission) -> Self {
+        let targets = submission
+            .issue_numbers
+            .iter()
+            .map(|&issue_number| ClaimTarget {
+                repo_owner: submission.repo_owner.clone(),
+                repo_name: submission.repo_name.clone(),
+                issue_number,
+            })
+            .collect();
+        Self {
+            hotkey: submission.hotkey,
+            github_username: submission.github_username,
+            targets,
+            signature: submission.signature,
+            timestamp: submission.timestamp,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RegisterRequest {
     pub hotkey: String,
@@ -37,8 +78,32 @@ pub struct IssueRecord {
     pub is_closed: bool,
     pub has_valid_label: bool,
     pub has_invalid_label: bool,
+    pub labels: Vec<String>,
     pub claimed_by_hotkey: Option<String>,
     pub recorded_epoch: u64,
+    pub created_at: Option<i64>,
+    pub closed_at: Option<i64>,
+    pub fingerprint: Option<Fingerprint>,
+}
+
+/// Title and body of a synced issue, kept out of `IssueRecord` so sync
+/// proposals stay small. Only read when the issue is assessed for quality.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IssueContent {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub issue_number: u32,
+    pub title: String,
+    pub body: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CreditedIssue {
+    pub issue_number: u32,
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub recorded_epoch: u64,
+    pub points: Ppb,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -54,6 +119,7 @@ pub struct InvalidIssueRecord {
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct UserBalance {
     pub valid_count: u32,
+    pub valid_points: Ppb,
     pub invalid_count: u32,
     pub duplicate_count: u32,
     pub star_count: u32,
@@ -65,17 +131,34 @@ pub struct LeaderboardEntry {
     pub rank: u32,
     pub hotkey: String,
     pub github_username: String,
-    pub score: f64,
+    pub score: Ppb,
+    pub lifetime_points: Ppb,
+    pub decayed_points: Ppb,
     pub valid_issues: u32,
     pub invalid_issues: u32,
     pub pending_issues: u32,
     pub star_count: u32,
-    pub star_bonus: f64,
-    pub net_points: f64,
+    pub star_bonus: Ppb,
+    pub net_points: Ppb,
     pub is_penalized: bool,
+    pub achieved_epoch: u64,
     pub last_epoch: u64,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LeaderboardSnapshot {
+    pub epoch: u64,
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RankHistoryEntry {
+    pub epoch: u64,
+    pub rank: u32,
+    pub score: Ppb,
+    pub net_points: Ppb,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct StatsResponse {
     pub total_bounties: u64,
@@ -90,27 +173,202 @@ pub struct StatusResponse {
     pub github_username: Option<String>,
     pub valid_issues_count: u32,
     pub invalid_issues_count: u32,
+    pub pending_issues_count: u32,
     pub balance: UserBalance,
-    pub weight: f64,
+    pub weight: Ppb,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ClaimResult {
     pub claimed: Vec<ClaimedIssue>,
     pub rejected: Vec<RejectedIssue>,
+    pub repos: Vec<RepoClaimOutcome>,
     pub total_valid: u32,
-    pub score: f64,
+    pub score: Ppb,
+    pub quota: ClaimQuota,
+}
+
+/// Dry-run outcome of a claim. `result` lists what would be claimed and
+/// rejected, with `total_valid` and `score` as they would be afterwards;
+/// `balance` and `weight` are projected the same way.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClaimPreview {
+    pub result: ClaimResult,
+    pub balance: UserBalance,
+    pub weight: Ppb,
+}
+
+/// What a hotkey may still submit in the current epoch.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ClaimQuota {
+    pub remaining_submissions: u32,
+    pub max_issues_per_submission: u32,
+    pub cooldown_until_epoch: Option<u64>,
+}
+
+/// Claim limits per hotkey. A hotkey whose last
+/// `max_consecutive_rejections` claims credited nothing cannot claim for
+/// `cooldown_epochs` epochs; zero disables the cooldown.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ClaimLimits {
+    pub max_issues_per_submission: u32,
+    pub max_submissions_per_epoch: u32,
+    pub max_consecutive_rejections: u32,
+    pub cooldown_epochs: u64,
+}
+
+impl Default for ClaimLimits {
+    fn default() -> Self {
+        Self {
+            max_issues_per_submission: 100,
+            max_submissions_per_epoch: 20,
+            max_consecutive_rejections: 5,
+            cooldown_epochs: 1,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ClaimActivity {
+    pub epoch: u64,
+    pub submissions: u32,
+    pub consecutive_rejections: u32,
+    pub cooldown_until_epoch: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ClaimedIssue {
+    pub repo_owner: String,
+    pub repo_name: String,
     pub issue_number: u32,
+    pub duplicate_of: Option<u32>,
+}
+
+/// Machine-readable reason a claimed issue was rejected. Variants are
+/// encoded by position, so new codes are only ever appended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RejectionCode {
+    NotFound,
+    NotClosed,
+    MissingValidLabel,
+    HasInvalidLabel,
+    AuthorMismatch,
+    AlreadyClaimed,
+    RecordFailed,
+    RepositoryNotOpen,
+    TooManyIssues,
+    SubmissionLimitReached,
+    CoolingDown,
+    CreatedBeforeRegistration,
+    CreatedBeforeProgramStart,
+    NearDuplicate,
+    CreationTimeUnknown,
+    RegistrationTimeUnknown,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DuplicateAction {
+    Flag,
+    Reject,
+}
+
+/// A claimed issue whose fingerprint is within `max_distance` bits of an
+/// earlier credited issue in the same repository is flagged or rejected.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DuplicateConfig {
+    pub max_distance: u32,
+    pub action: DuplicateAction,
+}
+
+impl Default for DuplicateConfig {
+    fn default() -> Self {
+        Self {
+            max_distance: 3,
+            action: DuplicateAction::Flag,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DuplicateLink {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub issue_number: u32,
+    pub original_issue_number: u32,
+    pub distance: u32,
+    pub hotkey: String,
+    pub action: DuplicateAction,
+    pub epoch: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RejectedIssue {
+    pub repo_owner: String,
+    pub repo_name: String,
     pub issue_number: u32,
-    pub reason: String,
+    pub code: RejectionCode,
+    pub message: Option<String>,
+    pub duplicate_of: Option<u32>,
+}
+
+/// Claimed and rejected counts for one repository of a claim.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RepoClaimOutcome {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub claimed: u32,
+    pub rejected: u32,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct WeightsResponse {
+    pub weights: Vec<crate::scoring::WeightAssignment>,
+    pub max_weight_share: Option<Ppb>,
+    pub burn_hotkey: Option<String>,
+    pub burn_fraction: Ppb,
+    pub ema_alpha: Option<Ppb>,
+}
+
+/// Smoothed weights as of the end of `epoch`.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct WeightEmaState {
+    pub epoch: u64,
+    pub weights: Vec<crate::scoring::WeightAssignment>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UidMapping {
+    pub hotkey: String,
+    pub uid: u16,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ChainWeight {
+    pub hotkey: String,
+    pub uid: Option<u16>,
+    pub weight: u16,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BalanceAdjustment {
+    pub hotkey: String,
+    pub additional_valid_issues: u32,
+    pub points_per_issue: Option<Ppb>,
+    pub additional_invalid_issues: u32,
+    pub star_count: Option<u32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulationRequest {
+    pub adjustments: Vec<BalanceAdjustment>,
+    pub scoring: Option<ScoringConfig>,
+    pub decay: Option<DecayConfig>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SimulationResponse {
+    pub leaderboard: Vec<LeaderboardEntry>,
+    pub weights: Vec<crate::scoring::WeightAssignment>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -144,4 +402,337 @@ impl Default for TimeoutConfig {
     }
 }
 
+/// Freshness rules for signed registrations and claims.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AuthConfig {
+    pub timestamp_window_secs: u64,
+    pub replay_cache_size: u32,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        Self {
+            timestamp_window_secs: 300,
+            replay_cache_size: 64,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SeenSignature {
+    pub timestamp: i64,
+    pub signature: Vec<u8>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ScoringConfig {
+    pub weight_per_point: Ppb,
+    pub star_bonus_per_repo: Ppb,
+    pub max_weight_share: Option<Ppb>,
+    pub burn_hotkey: Option<String>,
+    pub burn_fraction: Ppb,
+    pub ema_alpha: Option<Ppb>,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            weight_per_point: crate::scoring::WEIGHT_PER_POINT,
+            star_bonus_per_repo: crate::scoring::STAR_BONUS_PER_REPO,
+            max_weight_share: None,
+            burn_hotkey: None,
+            burn_fraction: 0,
+            ema_alpha: None,
+        }
+    }
+}
+
+/// Exponential decay applied to credited issues by age in epochs.
+/// A `half_life_epochs` of 0 disables decay.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct DecayConfig {
+    pub half_life_epochs: u64,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RepoMultiplier {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub multiplier: Ppb,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LabelMultiplier {
+    pub label: String,
+    pub multiplier: Ppb,
+}
+
+/// Point multipliers applied when a claim is credited, in parts per billion.
+/// Repositories and component labels without an entry use a multiplier of 1.0.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PointMultipliers {
+    pub repos: Vec<RepoMultiplier>,
+    pub labels: Vec<LabelMultiplier>,
+}
+
+impl Default for PointMultipliers {
+    fn default() -> Self {
+        Self {
+            repos: Vec::new(),
+            labels: alloc::vec![
+                LabelMultiplier {
+                    label: String::from("cortex"),
+                    multiplier: 5 * SCALE,
+                },
+                LabelMultiplier {
+                    label: String::from("term-challenge"),
+                    multiplier: SCALE,
+                },
+                LabelMultiplier {
+                    label: String::from("vgrep"),
+                    multiplier: SCALE,
+                },
+            ],
+        }
+    }
+}
+
+/// Host times at which the first and the latest observed epochs were first
+/// seen. Used to estimate when an earlier epoch began.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EpochClock {
+    pub first_epoch: u64,
+    pub first_seen_at: i64,
+    pub last_epoch: u64,
+    pub last_seen_at: i64,
+}
+
+impl EpochClock {
+    /// Estimated start of `epoch`, extrapolated at the average epoch length
+    /// observed between the two anchors. Until a second epoch has been seen
+    /// the first anchor is used, which errs towards a later time.
+    pub fn estimate_start(&self, epoch: u64) -> i64 {
+        let observed = self.last_epoch.saturating_sub(self.first_epoch);
+        if observed == 0 {
+            return self.first_seen_at;
+        }
+        let length = self.last_seen_at.saturating_sub(self.first_seen_at) / observed as i64;
+        let offset = epoch as i64 - self.first_epoch as i64;
+        self.first_seen_at
+            .saturating_add(offset.saturating_mul(length))
+    }
+}
+
+/// A repository that is part of the bounty program. Issues can be claimed
+/// while it is active and the current epoch is within
+/// `[start_epoch, end_epoch]`. Issues created before `program_start_at`,
+/// or before the claimant registered when
+/// `require_created_after_registration` is set, are not claimable.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RepositoryEntry {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub active: bool,
+    pub start_epoch: u64,
+    pub end_epoch: Option<u64>,
+    pub program_start_at: Option<i64>,
+    pub require_created_after_registration: bool,
+}
+
+impl RepositoryEntry {
+    pub fn matches(&self, repo_owner: &str, repo_name: &str) -> bool {
+        self.repo_owner.eq_ignore_ascii_case(repo_owner)
+            && self.repo_name.eq_ignore_ascii_case(repo_name)
+    }
+
+    pub fn is_claimable_at(&self, epoch: u64) -> bool {
+        self.active && epoch >= self.start_epoch && self.end_epoch.is_none_or(|end| epoch <= end)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RepositoryRegistry {
+    pub repos: Vec<RepositoryEntry>,
+}
+
+impl RepositoryRegistry {
+    pub fn find(&self, repo_owner: &str, repo_name: &str) -> Option<&RepositoryEntry> {
+        self.repos.iter().find(|r| r.matches(repo_owner, repo_name))
+    }
+}
+
+impl Default for RepositoryRegistry {
+    fn default() -> Self {
+        let repo = |repo_owner: &str, repo_name: &str| RepositoryEntry {
+            repo_owner: String::from(repo_owner),
+            repo_name: String::from(repo_name),
+            active: true,
+            start_epoch: 0,
+            end_epoch: None,
+            program_start_at: None,
+            require_created_after_registration: true,
+        };
+        Self {
+            repos: alloc::vec![
+                repo("PlatformNetwork", "bounty-challenge"),
+                repo("PlatformNetwork", "platform"),
+                repo("PlatformNetwork", "term-challenge"),
+                repo("CortexLM", "cortex"),
+                repo("CortexLM", "vgrep"),
+            ],
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct StarTarget {
+    pub repo_owner: String,
+    pub repo_name: String,
+}
+
+impl StarTarget {
+    pub fn new(repo_owner: &str, repo_name: &str) -> Self {
+        Self {
+            repo_owner: String::from(repo_owner),
+            repo_name: String::from(repo_name),
+        }
+    }
+
+    pub fn matches(&self, other: &StarTarget) -> bool {
+        self.repo_owner.eq_ignore_ascii_case(&other.repo_owner)
+            && self.repo_name.eq_ignore_ascii_case(&other.repo_name)
+    }
+}
+
+/// Hotkeys of the validators whose proposals count toward consensus.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct ValidatorSet {
+    pub hotkeys: Vec<String>,
+}
+
+/// Repositories that earn the star bonus when starred.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StarTargets {
+    pub repos: Vec<StarTarget>,
+}
+
+impl Default for StarTargets {
+    fn default() -> Self {
+        Self {
+            repos: alloc::vec![
+                StarTarget::new("CortexLM", "vgrep"),
+                StarTarget::new("CortexLM", "cortex"),
+                StarTarget::new("PlatformNetwork", "platform"),
+                StarTarget::new("PlatformNetwork", "term-challenge"),
+                StarTarget::new("PlatformNetwork", "bounty-challenge"),
+            ],
+        }
+    }
+}
+
+/// A validator's view of which star targets a GitHub user has starred.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StarProposal {
+    pub validator_id: String,
+    pub github_username: String,
+    pub starred: Vec<StarTarget>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UserStars {
+    pub github_username: String,
+    pub starred: Vec<StarTarget>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LabelTier {
+    pub label: String,
+    pub points: Ppb,
+}
+
+/// Severity tiers keyed by label. A credited issue earns the points of its
+/// highest matching tier, or `base_points` when no tier label is present.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct LabelTiers {
+    pub base_points: Ppb,
+    pub tiers: Vec<LabelTier>,
+}
+
+impl Default for LabelTiers {
+    fn default() -> Self {
+        Self {
+            base_points: SCALE,
+            tiers: Vec::new(),
+        }
+    }
+}
+
 pub use platform_challenge_sdk_wasm::{LlmMessage, LlmRequest, LlmResponse};
+
+/// Optional LLM review of claimed issues. Low-quality issues are queued for
+/// review; their points are never changed, since each validator's LLM may
+/// answer differently.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QualityConfig {
+    pub enabled: bool,
+    pub model: String,
+    pub max_tokens: u32,
+    pub temperature: Ppb,
+    pub review_below: u32,
+}
+
+impl Default for QualityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            model: String::new(),
+            max_tokens: 256,
+            temperature: 0,
+            review_below: 50,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum QualityVerdict {
+    Accept,
+    Review,
+    Reject,
+}
+
+/// Score from 0 to 100 and verdict parsed from the LLM's reply.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QualityAssessment {
+    pub score: u32,
+    pub verdict: QualityVerdict,
+}
+
+/// A credited issue waiting for the validator set to assess its quality.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingAssessment {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub issue_number: u32,
+    pub hotkey: String,
+    pub points: Ppb,
+    pub epoch: u64,
+}
+
+/// One validator's assessment of a queued issue. `assessment` is `None` when
+/// its LLM gave no usable reply, so the issue is not retried.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QualityProposal {
+    pub validator_id: String,
+    pub assessment: Option<QualityAssessment>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct QualityReview {
+    pub repo_owner: String,
+    pub repo_name: String,
+    pub issue_number: u32,
+    pub hotkey: String,
+    pub assessment: QualityAssessment,
+    pub points: Ppb,
+    pub epoch: u64,
+}
@@ -1,115 +1,686 @@
 use alloc::string::String;
 use alloc::vec::Vec;
+use bincode::Options;
 
-use crate::storage;
-use crate::types::{BountySubmission, ClaimResult, ClaimedIssue, IssueRecord, RejectedIssue};
+use crate::fingerprint::{hamming_distance, Fingerprint};
+use crate::fixed::Ppb;
+use crate::types::{
+    BountySubmission, ClaimActivity, ClaimLimits, ClaimPreview, ClaimQuota, ClaimResult,
+    ClaimTarget, ClaimedIssue, CreditedIssue, DuplicateAction, DuplicateConfig, DuplicateLink,
+    IssueRecord, MultiRepoSubmission, PendingAssessment, RejectedIssue, RejectionCode,
+    RepoClaimOutcome, RepositoryEntry, RepositoryRegistry,
+};
+use crate::{signature, storage};
 
-pub fn validate_submission(submission: &BountySubmission) -> bool {
+/// Prefix of a tagged claim envelope, followed by one format byte and the
+/// bincode-encoded submission. Read as the leading `u64` length of an
+/// untagged submission's hotkey it is far beyond any size limit, so an
+/// envelope can never be mistaken for an untagged claim.
+pub const CLAIM_ENVELOPE_MAGIC: &[u8; 7] = b"BNTYCLM";
+pub const CLAIM_FORMAT_SINGLE: u8 = 1;
+pub const CLAIM_FORMAT_MULTI: u8 = 2;
+
+/// A claim in either wire format. The signature covers the format the miner
+/// signed, so the original is kept until it has been verified.
+pub enum ClaimPayload {
+    Single(BountySubmission),
+    Multi(MultiRepoSubmission),
+}
+
+impl ClaimPayload {
+    /// Decodes the format named by the envelope's format byte, rejecting
+    /// trailing bytes. Data without the envelope prefix is read with the
+    /// lenient single-repository decoding older clients relied on.
+    pub fn decode(data: &[u8], limit: u64) -> Option<Self> {
+        let options = || {
+            bincode::DefaultOptions::new()
+                .with_limit(limit)
+                .with_fixint_encoding()
+        };
+        let Some(tagged) = data.strip_prefix(CLAIM_ENVELOPE_MAGIC.as_slice()) else {
+            return options()
+                .allow_trailing_bytes()
+                .deserialize::<BountySubmission>(data)
+                .ok()
+                .map(Self::Single);
+        };
+        let (&format, body) = tagged.split_first()?;
+        match format {
+            CLAIM_FORMAT_SINGLE => options().deserialize(body).ok().map(Self::Single),
+            CLAIM_FORMAT_MULTI => options().deserialize(body).ok().map(Self::Multi),
+            _ => None,
+        }
+    }
+
+    pub fn verify_signature(&self) -> bool {
+        match self {
+            Self::Single(submission) => signature::verify_submission(submission),
+            Self::Multi(submission) => signature::verify_multi_submission(submission),
+        }
+    }
+
+    pub fn into_submission(self) -> MultiRepoSubmission {
+        match self {
+            Self::Single(submission) => submission.into(),
+            Self::Multi(submission) => submission,
+        }
+    }
+}
+
+pub fn validate_submission(submission: &MultiRepoSubmission) -> bool {
     if submission.hotkey.is_empty() {
         return false;
     }
     if submission.github_username.is_empty() {
         return false;
     }
-    if submission.issue_numbers.is_empty() {
+    if submission.targets.is_empty() {
         return false;
     }
     if submission.signature.is_empty() {
         return false;
     }
-    if submission.repo_owner.is_empty() || submission.repo_name.is_empty() {
+    if submission
+        .targets
+        .iter()
+        .any(|t| t.repo_owner.is_empty() || t.repo_name.is_empty())
+    {
         return false;
     }
-    true
+    signature::is_timestamp_fresh(submission.timestamp, &storage::get_auth_config())
+}
+
+/// Whether `submission.hotkey` is registered to `submission.github_username`,
+/// as `evaluate` requires before processing a claim.
+pub fn matches_registration(submission: &MultiRepoSubmission) -> bool {
+    storage::get_user_by_hotkey(&submission.hotkey).is_some_and(|reg| {
+        reg.github_username
+            .eq_ignore_ascii_case(&submission.github_username)
+    })
 }
 
-pub fn validate_issue(issue: &IssueRecord, expected_author: &str) -> (bool, Option<String>) {
+pub fn validate_issue(
+    issue: &IssueRecord,
+    expected_author: &str,
+) -> Result<(), (RejectionCode, String)> {
     if !issue.is_closed {
-        return (false, Some(String::from("Issue is not closed")));
+        return Err((
+            RejectionCode::NotClosed,
+            String::from("Issue is not closed"),
+        ));
     }
     if !issue.has_valid_label {
-        return (false, Some(String::from("Issue missing 'valid' label")));
+        return Err((
+            RejectionCode::MissingValidLabel,
+            String::from("Issue missing 'valid' label"),
+        ));
     }
     if issue.has_invalid_label {
-        return (false, Some(String::from("Issue has 'invalid' label")));
+        return Err((
+            RejectionCode::HasInvalidLabel,
+            String::from("Issue has 'invalid' label"),
+        ));
     }
     if issue.author.to_lowercase() != expected_author.to_lowercase() {
         let mut msg = String::from("Author mismatch: expected ");
         msg.push_str(expected_author);
         msg.push_str(", got ");
         msg.push_str(&issue.author);
-        return (false, Some(msg));
+        return Err((RejectionCode::AuthorMismatch, msg));
     }
     if issue.claimed_by_hotkey.is_some() {
-        return (false, Some(String::from("Issue already claimed")));
+        return Err((
+            RejectionCode::AlreadyClaimed,
+            String::from("Issue already claimed"),
+        ));
     }
-    (true, None)
+    Ok(())
+}
+
+/// When `hotkey` registered: the host time recorded at registration, or for
+/// registrations made before that was recorded, the estimated start of their
+/// `registered_epoch`.
+pub fn registration_time(hotkey: &str) -> Option<i64> {
+    storage::get_registered_at(hotkey).or_else(|| {
+        let registration = storage::get_user_by_hotkey(hotkey)?;
+        Some(storage::get_epoch_clock()?.estimate_start(registration.registered_epoch))
+    })
+}
+
+/// Applies the repository's issue age policy. An issue whose creation time
+/// was not synced, or a claimant whose registration time is unknown, cannot
+/// pass a policy that applies to it.
+pub fn validate_issue_age(
+    issue: &IssueRecord,
+    repo: &RepositoryEntry,
+    registered_at: Option<i64>,
+) -> Result<(), (RejectionCode, String)> {
+    if repo.program_start_at.is_none() && !repo.require_created_after_registration {
+        return Ok(());
+    }
+    let created_at = match issue.created_at {
+        Some(t) => t,
+        None => {
+            return Err((
+                RejectionCode::CreationTimeUnknown,
+                String::from("Issue creation time is unknown"),
+            ))
+        }
+    };
+
+    if let Some(start) = repo.program_start_at {
+        if created_at < start {
+            return Err((
+                RejectionCode::CreatedBeforeProgramStart,
+                String::from("Issue was created before the repository joined the program"),
+            ));
+        }
+    }
+    if repo.require_created_after_registration {
+        let registered = registered_at.ok_or((
+            RejectionCode::RegistrationTimeUnknown,
+            String::from("Hotkey registration time is unknown"),
+        ))?;
+        if created_at < registered {
+            return Err((
+                RejectionCode::CreatedBeforeRegistration,
+                String::from("Issue was created before the hotkey registered"),
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Entries need an owner and name, an end epoch not before the start, and
+/// no repository may be listed twice.
+pub fn validate_repo_registry(registry: &RepositoryRegistry) -> bool {
+    registry.repos.iter().enumerate().all(|(i, repo)| {
+        !repo.repo_owner.is_empty()
+            && !repo.repo_name.is_empty()
+            && repo.end_epoch.is_none_or(|end| end >= repo.start_epoch)
+            && !registry.repos[..i]
+                .iter()
+                .any(|r| r.matches(&repo.repo_owner, &repo.repo_name))
+    })
+}
+
+pub fn validate_claim_limits(limits: &ClaimLimits) -> bool {
+    limits.max_issues_per_submission > 0 && limits.max_submissions_per_epoch > 0
+}
+
+/// Activity with the per-epoch submission count reset once the epoch moves on.
+fn current_activity(hotkey: &str, epoch: u64) -> ClaimActivity {
+    let mut activity = storage::get_claim_activity(hotkey);
+    if activity.epoch != epoch {
+        activity.epoch = epoch;
+        activity.submissions = 0;
+    }
+    activity
+}
+
+pub fn claim_quota(hotkey: &str, limits: &ClaimLimits, epoch: u64) -> ClaimQuota {
+    let activity = current_activity(hotkey, epoch);
+    ClaimQuota {
+        remaining_submissions: limits
+            .max_submissions_per_epoch
+            .saturating_sub(activity.submissions),
+        max_issues_per_submission: limits.max_issues_per_submission,
+        cooldown_until_epoch: Some(activity.cooldown_until_epoch).filter(|&until| until > epoch),
+    }
+}
+
+/// Checks the claim limits before any issue is looked at. Claims refused
+/// here do not count against the quota.
+pub fn check_claim_limits(
+    submission: &MultiRepoSubmission,
+    limits: &ClaimLimits,
+    epoch: u64,
+) -> Result<(), RejectionCode> {
+    if submission.targets.len() > limits.max_issues_per_submission as usize {
+        return Err(RejectionCode::TooManyIssues);
+    }
+    let activity = current_activity(&submission.hotkey, epoch);
+    if activity.cooldown_until_epoch > epoch {
+        return Err(RejectionCode::CoolingDown);
+    }
+    if activity.submissions >= limits.max_submissions_per_epoch {
+        return Err(RejectionCode::SubmissionLimitReached);
+    }
+    Ok(())
+}
+
+fn record_claim_activity(hotkey: &str, credited_any: bool, limits: &ClaimLimits, epoch: u64) {
+    let mut activity = current_activity(hotkey, epoch);
+    activity.submissions = activity.submissions.saturating_add(1);
+    if credited_any {
+        activity.consecutive_rejections = 0;
+    } else {
+        activity.consecutive_rejections = activity.consecutive_rejections.saturating_add(1);
+        if limits.max_consecutive_rejections > 0
+            && limits.cooldown_epochs > 0
+            && activity.consecutive_rejections >= limits.max_consecutive_rejections
+        {
+            activity.cooldown_until_epoch = epoch.saturating_add(limits.cooldown_epochs);
+            activity.consecutive_rejections = 0;
+        }
+    }
+    storage::store_claim_activity(hotkey, &activity);
+}
+
+/// Valid issue count and current score of `hotkey`.
+fn claimant_standing(hotkey: &str) -> (u32, Ppb) {
+    let balance = storage::get_user_balance(hotkey);
+    let (_, decayed_points) = crate::scoring::hotkey_issue_points(hotkey, &balance);
+    let score = crate::scoring::calculate_weight_from_points(
+        decayed_points,
+        balance.star_count,
+        &storage::get_scoring_config(),
+    );
+    (balance.valid_count, score)
+}
+
+/// Result for a claim refused by `check_claim_limits`: every target is
+/// rejected with `code` and nothing is recorded.
+pub fn limited_claim_result(
+    submission: &MultiRepoSubmission,
+    code: RejectionCode,
+    limits: &ClaimLimits,
+    epoch: u64,
+) -> ClaimResult {
+    let message = match code {
+        RejectionCode::TooManyIssues => "Too many issues in one claim",
+        RejectionCode::CoolingDown => "Claims paused after repeated rejections",
+        _ => "Claim limit for this epoch reached",
+    };
+    let rejected: Vec<RejectedIssue> = submission
+        .targets
+        .iter()
+        .map(|t| rejection(t, code, String::from(message)))
+        .collect();
+    let (total_valid, score) = claimant_standing(&submission.hotkey);
+
+    ClaimResult {
+        repos: repo_outcomes(&submission.targets, &[], &rejected),
+        claimed: Vec::new(),
+        rejected,
+        total_valid,
+        score,
+        quota: claim_quota(&submission.hotkey, limits, epoch),
+    }
+}
+
+fn rejection(target: &ClaimTarget, code: RejectionCode, message: String) -> RejectedIssue {
+    RejectedIssue {
+        repo_owner: target.repo_owner.clone(),
+        repo_name: target.repo_name.clone(),
+        issue_number: target.issue_number,
+        code,
+        message: Some(message),
+        duplicate_of: None,
+    }
+}
+
+/// Closest earlier credited issue in the same repository whose fingerprint is
+/// within `max_distance` bits, as `(issue_number, distance)`. `pending` are
+/// issues credited earlier in the same claim. Ties go to the lowest issue
+/// number, which is normally the original report.
+fn find_near_duplicate(
+    issue: &IssueRecord,
+    fingerprint: Fingerprint,
+    pending: &[(&IssueRecord, Ppb)],
+    config: &DuplicateConfig,
+) -> Option<(u32, u32)> {
+    let pending = pending
+        .iter()
+        .filter(|(p, _)| p.repo_owner == issue.repo_owner && p.repo_name == issue.repo_name)
+        .filter_map(|(p, _)| Some((p.issue_number, p.fingerprint?)));
+    storage::get_issue_fingerprints(&issue.repo_owner, &issue.repo_name)
+        .into_iter()
+        .chain(pending)
+        .filter(|&(number, _)| number != issue.issue_number)
+        .map(|(number, other)| (number, hamming_distance(fingerprint, other)))
+        .filter(|&(_, distance)| distance <= config.max_distance)
+        .min_by_key(|&(number, distance)| (distance, number))
 }
 
-pub fn process_claims(submission: &BountySubmission, synced_issues: &[IssueRecord]) -> ClaimResult {
+pub fn validate_duplicate_config(config: &DuplicateConfig) -> bool {
+    config.max_distance <= 64
+}
+
+/// Tallies claimed and rejected issues per repository, in the order each
+/// repository first appears in the claim.
+fn repo_outcomes(
+    targets: &[ClaimTarget],
+    claimed: &[ClaimedIssue],
+    rejected: &[RejectedIssue],
+) -> Vec<RepoClaimOutcome> {
+    let mut outcomes: Vec<RepoClaimOutcome> = Vec::new();
+    for target in targets {
+        if !outcomes
+            .iter()
+            .any(|o| o.repo_owner == target.repo_owner && o.repo_name == target.repo_name)
+        {
+            outcomes.push(RepoClaimOutcome {
+                repo_owner: target.repo_owner.clone(),
+                repo_name: target.repo_name.clone(),
+                claimed: 0,
+                rejected: 0,
+            });
+        }
+    }
+    for outcome in &mut outcomes {
+        let in_repo =
+            |owner: &str, name: &str| owner == outcome.repo_owner && name == outcome.repo_name;
+        outcome.claimed = claimed
+            .iter()
+            .filter(|c| in_repo(&c.repo_owner, &c.repo_name))
+            .count() as u32;
+        outcome.rejected = rejected
+            .iter()
+            .filter(|r| in_repo(&r.repo_owner, &r.repo_name))
+            .count() as u32;
+    }
+    outcomes
+}
+
+/// Targets of a claim sorted into claimed and rejected, with the issues
+/// credited, or that would be credited, and their points.
+struct ClaimRun<'a> {
+    claimed: Vec<ClaimedIssue>,
+    rejected: Vec<RejectedIssue>,
+    credited: Vec<(&'a IssueRecord, Ppb)>,
+}
+
+/// Checks each target independently, so one rejected issue never affects the
+/// others in the same claim. Without `record` nothing is written or queued
+/// for quality assessment; issues credited earlier in the same claim still
+/// count as claimed, so the outcome matches a recorded run.
+fn run_claims<'a>(
+    submission: &MultiRepoSubmission,
+    synced_issues: &'a [IssueRecord],
+    record: bool,
+) -> ClaimRun<'a> {
     let mut claimed = Vec::new();
     let mut rejected = Vec::new();
+    let mut credited: Vec<(&IssueRecord, Ppb)> = Vec::new();
+    let multipliers = storage::get_point_multipliers();
+    let tiers = storage::get_label_tiers();
+    let registry = storage::get_repo_registry();
+    let epoch = crate::scoring::current_epoch();
 
-    for &issue_number in &submission.issue_numbers {
-        if storage::is_issue_recorded(&submission.repo_owner, &submission.repo_name, issue_number) {
-            rejected.push(RejectedIssue {
-                issue_number,
-                reason: String::from("Issue already claimed"),
-            });
+    let registered_at = registration_time(&submission.hotkey);
+    let duplicates = storage::get_duplicate_config();
+    let quality_enabled = storage::get_quality_config().enabled;
+
+    for target in &submission.targets {
+        let repo = match registry
+            .find(&target.repo_owner, &target.repo_name)
+            .filter(|r| r.is_claimable_at(epoch))
+        {
+            Some(r) => r,
+            None => {
+                rejected.push(rejection(
+                    target,
+                    RejectionCode::RepositoryNotOpen,
+                    String::from("Repository is not open for claims"),
+                ));
+                continue;
+            }
+        };
+
+        let is_target = |i: &IssueRecord| {
+            i.issue_number == target.issue_number
+                && i.repo_owner == target.repo_owner
+                && i.repo_name == target.repo_name
+        };
+
+        if storage::is_issue_recorded(&target.repo_owner, &target.repo_name, target.issue_number)
+            || credited.iter().any(|(i, _)| is_target(i))
+        {
+            rejected.push(rejection(
+                target,
+                RejectionCode::AlreadyClaimed,
+                String::from("Issue already claimed"),
+            ));
             continue;
         }
 
-        let issue = synced_issues.iter().find(|i| {
-            i.issue_number == issue_number
-                && i.repo_owner == submission.repo_owner
-                && i.repo_name == submission.repo_name
-        });
+        let issue = synced_issues.iter().find(|i| is_target(i));
 
         match issue {
-            Some(issue_record) => {
-                let (valid, reason) = validate_issue(issue_record, &submission.github_username);
-
-                if valid {
-                    let recorded = storage::record_valid_issue(
-                        issue_number,
-                        &submission.repo_owner,
-                        &submission.repo_name,
-                        &submission.github_username,
-                        &submission.hotkey,
-                    );
+            Some(issue_record) => match validate_issue(issue_record, &submission.github_username)
+                .and_then(|()| validate_issue_age(issue_record, repo, registered_at))
+            {
+                Ok(()) => {
+                    let duplicate = issue_record
+                        .fingerprint
+                        .and_then(|f| find_near_duplicate(issue_record, f, &credited, &duplicates));
+                    let link = |(original, distance): (u32, u32)| DuplicateLink {
+                        repo_owner: target.repo_owner.clone(),
+                        repo_name: target.repo_name.clone(),
+                        issue_number: target.issue_number,
+                        original_issue_number: original,
+                        distance,
+                        hotkey: submission.hotkey.clone(),
+                        action: duplicates.action,
+                        epoch,
+                    };
 
-                    if recorded {
-                        claimed.push(ClaimedIssue { issue_number });
-                    } else {
+                    if let Some(found) =
+                        duplicate.filter(|_| duplicates.action == DuplicateAction::Reject)
+                    {
+                        if record {
+                            storage::add_duplicate_link(link(found));
+                        }
                         rejected.push(RejectedIssue {
-                            issue_number,
-                            reason: String::from("Failed to record issue"),
+                            duplicate_of: Some(found.0),
+                            ..rejection(
+                                target,
+                                RejectionCode::NearDuplicate,
+                                alloc::format!("Near-duplicate of #{}", found.0),
+                            )
+                        });
+                        continue;
+                    }
+
+                    let points =
+                        crate::scoring::calculate_issue_value(issue_record, &multipliers, &tiers);
+                    let recorded = !record
+                        || storage::record_valid_issue(issue_record, &submission.hotkey, points);
+
+                    if recorded {
+                        if let Some(found) = duplicate.filter(|_| record) {
+                            storage::add_duplicate_link(link(found));
+                        }
+                        if record && quality_enabled {
+                            storage::queue_assessment(PendingAssessment {
+                                repo_owner: target.repo_owner.clone(),
+                                repo_name: target.repo_name.clone(),
+                                issue_number: target.issue_number,
+                                hotkey: submission.hotkey.clone(),
+                                points,
+                                epoch,
+                            });
+                        }
+                        credited.push((issue_record, points));
+                        claimed.push(ClaimedIssue {
+                            repo_owner: target.repo_owner.clone(),
+                            repo_name: target.repo_name.clone(),
+                            issue_number: target.issue_number,
+                            duplicate_of: duplicate.map(|(original, _)| original),
                         });
+                    } else {
+                        rejected.push(rejection(
+                            target,
+                            RejectionCode::RecordFailed,
+                            String::from("Failed to record issue"),
+                        ));
                     }
-                } else {
-                    rejected.push(RejectedIssue {
-                        issue_number,
-                        reason: reason.unwrap_or_else(|| String::from("Validation failed")),
-                    });
                 }
-            }
+                Err((code, message)) => {
+                    rejected.push(rejection(target, code, message));
+                }
+            },
             None => {
-                rejected.push(RejectedIssue {
-                    issue_number,
-                    reason: String::from("Issue not found in synced data"),
-                });
+                rejected.push(rejection(
+                    target,
+                    RejectionCode::NotFound,
+                    String::from("Issue not found in synced data"),
+                ));
             }
         }
     }
 
-    let balance = storage::get_user_balance(&submission.hotkey);
-    let score =
-        crate::scoring::calculate_weight_from_points(balance.valid_count, balance.star_count);
-
-    ClaimResult {
+    ClaimRun {
         claimed,
         rejected,
-        total_valid: balance.valid_count,
+        credited,
+    }
+}
+
+/// Validates and records each target of a claim.
+pub fn process_claims(
+    submission: &MultiRepoSubmission,
+    synced_issues: &[IssueRecord],
+) -> ClaimResult {
+    let epoch = crate::scoring::current_epoch();
+    let limits = storage::get_claim_limits();
+    storage::observe_epoch(epoch, signature::host_now_secs());
+    let run = run_claims(submission, synced_issues, true);
+
+    let (total_valid, score) = claimant_standing(&submission.hotkey);
+    record_claim_activity(&submission.hotkey, !run.claimed.is_empty(), &limits, epoch);
+
+    ClaimResult {
+        repos: repo_outcomes(&submission.targets, &run.claimed, &run.rejected),
+        claimed: run.claimed,
+        rejected: run.rejected,
+        total_valid,
         score,
+        quota: claim_quota(&submission.hotkey, &limits, epoch),
+    }
+}
+
+/// Runs the same checks as a claim, including the claim limits, without
+/// recording anything, and projects the claimant's standing afterwards.
+pub fn preview_claims(
+    submission: &MultiRepoSubmission,
+    synced_issues: &[IssueRecord],
+) -> ClaimPreview {
+    let epoch = crate::scoring::current_epoch();
+    let limits = storage::get_claim_limits();
+
+    let (mut result, credited) = match check_claim_limits(submission, &limits, epoch) {
+        Err(code) => (
+            limited_claim_result(submission, code, &limits, epoch),
+            Vec::new(),
+        ),
+        Ok(()) => {
+            let run = run_claims(submission, synced_issues, false);
+            let credited = run
+                .credited
+                .iter()
+                .map(|(issue, points)| CreditedIssue {
+                    issue_number: issue.issue_number,
+                    repo_owner: issue.repo_owner.clone(),
+                    repo_name: issue.repo_name.clone(),
+                    recorded_epoch: epoch,
+                    points: *points,
+                })
+                .collect();
+            let result = ClaimResult {
+                repos: repo_outcomes(&submission.targets, &run.claimed, &run.rejected),
+                claimed: run.claimed,
+                rejected: run.rejected,
+                total_valid: 0,
+                score: 0,
+                quota: claim_quota(&submission.hotkey, &limits, epoch),
+            };
+            (result, credited)
+        }
+    };
+
+    let (balance, score, weight) = crate::scoring::project_claim(&submission.hotkey, &credited);
+    result.total_valid = balance.valid_count;
+    result.score = score;
+
+    ClaimPreview {
+        result,
+        balance,
+        weight,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIMIT: u64 = 64 * 1024;
+
+    fn envelope(format: u8, submission: &impl serde::Serialize) -> Vec<u8> {
+        let mut data = CLAIM_ENVELOPE_MAGIC.to_vec();
+        data.push(format);
+        data.extend(bincode::serialize(submission).unwrap());
+        data
+    }
+
+    fn single() -> BountySubmission {
+        BountySubmission {
+            hotkey: String::from("5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"),
+            github_username: String::from("alice"),
+            issue_numbers: alloc::vec![42, 43],
+            repo_owner: String::from("PlatformNetwork"),
+            repo_name: String::from("bounty-challenge"),
+            signature: alloc::vec![7; 64],
+            timestamp: 1_705_590_000,
+        }
+    }
+
+    #[test]
+    fn tagged_payloads_decode_in_their_format() {
+        let encoded = envelope(CLAIM_FORMAT_SINGLE, &single());
+        assert!(matches!(
+            ClaimPayload::decode(&encoded, LIMIT),
+            Some(ClaimPayload::Single(s)) if s.issue_numbers == [42, 43]
+        ));
+
+        let encoded = envelope(CLAIM_FORMAT_MULTI, &MultiRepoSubmission::from(single()));
+        assert!(matches!(
+            ClaimPayload::decode(&encoded, LIMIT),
+            Some(ClaimPayload::Multi(m)) if m.targets.len() == 2
+        ));
+    }
+
+    #[test]
+    fn tagged_payloads_reject_unknown_format_and_trailing_bytes() {
+        let mut encoded = envelope(CLAIM_FORMAT_SINGLE, &single());
+        encoded.push(0);
+        assert!(ClaimPayload::decode(&encoded, LIMIT).is_none());
+
+        let mut encoded = envelope(CLAIM_FORMAT_MULTI, &MultiRepoSubmission::from(single()));
+        encoded[CLAIM_ENVELOPE_MAGIC.len()] = 9;
+        assert!(ClaimPayload::decode(&encoded, LIMIT).is_none());
+        assert!(ClaimPayload::decode(CLAIM_ENVELOPE_MAGIC, LIMIT).is_none());
+    }
+
+    #[test]
+    fn untagged_payloads_decode_as_single() {
+        let mut legacy = bincode::serialize(&single()).unwrap();
+        assert!(matches!(
+            ClaimPayload::decode(&legacy, LIMIT),
+            Some(ClaimPayload::Single(_))
+        ));
+        legacy.extend_from_slice(&[0, 0]);
+        assert!(matches!(
+            ClaimPayload::decode(&legacy, LIMIT),
+            Some(ClaimPayload::Single(_))
+        ));
+
+        let multi: MultiRepoSubmission = single().into();
+        let untagged_multi = bincode::serialize(&multi).unwrap();
+        assert!(!matches!(
+            ClaimPayload::decode(&untagged_multi, LIMIT),
+            Some(ClaimPayload::Multi(_))
+        ));
     }
 }